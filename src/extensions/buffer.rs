@@ -0,0 +1,139 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+pub struct BufferObservable<O, I> {
+    count: usize,
+    skip: usize,
+    duplicate: Option<fn(&I) -> I>,
+    original: O,
+}
+
+pub trait BufferExt<'a>: Observable<'a> + Sized {
+    /// Emits the items in consecutive groups of `count`. A shorter last group is emitted on completion.
+    fn buffer(self, count: usize) -> BufferObservable<Self, Self::Item> {
+        assert!(count > 0, "buffer count must be positive");
+        BufferObservable { count, skip: count, duplicate: None, original: self }
+    }
+
+    /// Starts a new group every `skip` items, each holding up to `count` items. Groups overlap when
+    /// `skip < count`, which clones every item into each group holding it, and leave gaps when
+    /// `skip > count`.
+    fn buffer_with_skip(self, count: usize, skip: usize) -> BufferObservable<Self, Self::Item> where Self::Item: Clone {
+        assert!(count > 0 && skip > 0, "buffer count and skip must be positive");
+        BufferObservable { count, skip, duplicate: Some(Clone::clone), original: self }
+    }
+}
+
+impl<'a, O> BufferExt<'a> for O where O: Observable<'a> {}
+
+struct Buffers<I> {
+    seen: usize,
+    active: VecDeque<Vec<I>>,
+}
+
+impl<'a, O> Observable<'a> for BufferObservable<O, O::Item> where O: Observable<'a> + 'a, O::Item: Send + Sync + 'a {
+    type Item = Vec<O::Item>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let count = self.count;
+        let skip = self.skip;
+        let duplicate = self.duplicate;
+        let observer = BaseObserver::new(observer);
+        let buffers = Arc::new(Mutex::new(Buffers { seen: 0, active: VecDeque::new() }));
+        let next = {
+            let observer = observer.clone();
+            let buffers = buffers.clone();
+            move |item: O::Item| {
+                let full = {
+                    let mut buffers = buffers.lock().unwrap();
+                    if buffers.seen % skip == 0 {
+                        buffers.active.push_back(Vec::with_capacity(count));
+                    }
+                    buffers.seen += 1;
+                    // only overlapping groups need copies; the newest group takes the item itself
+                    if let Some((newest, older)) = buffers.active.make_contiguous().split_last_mut() {
+                        if let Some(duplicate) = duplicate {
+                            older.iter_mut().for_each(|buffer| buffer.push(duplicate(&item)));
+                        }
+                        newest.push(item);
+                    }
+                    if buffers.active.front().is_some_and(|buffer| buffer.len() >= count) {
+                        buffers.active.pop_front()
+                    } else {
+                        None
+                    }
+                };
+                if let Some(full) = full {
+                    observer.on_next(full)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let buffers = buffers.clone();
+            move || {
+                let rest: Vec<_> = buffers.lock().unwrap().active.drain(..).collect();
+                rest.into_iter()
+                    .filter(|buffer| !buffer.is_empty())
+                    .for_each(|buffer| observer.on_next(buffer));
+                observer.on_completed()
+            }
+        };
+        let error = move |error| {
+            buffers.lock().unwrap().active.clear();
+            observer.on_error(error)
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::TestObserver;
+
+    #[test]
+    fn it_works() {
+        let observer = TestObserver::<Vec<i32>, ()>::new();
+        factory::from_iter(1..=7).buffer(3).subscribe(observer.fork());
+        observer.assert_values(&[vec![1, 2, 3], vec![4, 5, 6], vec![7]]).assert_completed();
+    }
+
+    #[test]
+    fn non_clone_items() {
+        struct Reading(i32);
+
+        let observer = TestObserver::<Vec<i32>, ()>::new();
+        factory::from_iter((1..=5).map(Reading))
+            .buffer(2)
+            .map(|buffer| buffer.into_iter().map(|reading| reading.0).collect())
+            .subscribe(observer.fork());
+        observer.assert_values(&[vec![1, 2], vec![3, 4], vec![5]]).assert_completed();
+    }
+
+    #[test]
+    fn sliding() {
+        let observer = TestObserver::<Vec<String>, ()>::new();
+        factory::from_iter(vec!["a", "b", "c", "d"].into_iter().map(String::from))
+            .buffer_with_skip(3, 1)
+            .subscribe(observer.fork());
+        let expected: Vec<Vec<String>> = vec![vec!["a", "b", "c"], vec!["b", "c", "d"], vec!["c", "d"], vec!["d"]]
+            .into_iter()
+            .map(|buffer| buffer.into_iter().map(String::from).collect())
+            .collect();
+        observer.assert_values(&expected).assert_completed();
+    }
+
+    #[test]
+    fn hopping() {
+        let observer = TestObserver::<Vec<i32>, ()>::new();
+        factory::from_iter(1..=7).buffer_with_skip(2, 3).subscribe(observer.fork());
+        observer.assert_values(&[vec![1, 2], vec![4, 5], vec![7]]).assert_completed();
+    }
+}
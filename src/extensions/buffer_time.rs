@@ -0,0 +1,211 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Scheduler, Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub struct BufferTimeObservable<O> {
+    span: Duration,
+    max: Option<usize>,
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub trait BufferTimeExt<'a>: Observable<'a> + Sized {
+    /// Emits the items collected during each `span`, and the buffer in progress on completion.
+    /// Buffers are emitted even when empty, on ticks and on completion alike.
+    fn buffer_time(self, span: Duration) -> BufferTimeObservable<Self> {
+        self.buffer_time_on(span, Scheduler::new_thread())
+    }
+
+    fn buffer_time_on(self, span: Duration, scheduler: Scheduler) -> BufferTimeObservable<Self> {
+        BufferTimeObservable { span, max: None, scheduler, original: self }
+    }
+
+    /// Like `buffer_time`, but also emits as soon as the buffer holds `max` items, starting a new `span`.
+    fn buffer_time_or_count(self, span: Duration, max: usize) -> BufferTimeObservable<Self> {
+        self.buffer_time_or_count_on(span, max, Scheduler::new_thread())
+    }
+
+    fn buffer_time_or_count_on(self, span: Duration, max: usize, scheduler: Scheduler) -> BufferTimeObservable<Self> {
+        BufferTimeObservable { span, max: Some(max), scheduler, original: self }
+    }
+}
+
+impl<'a, O> BufferTimeExt<'a> for O where O: Observable<'a> {}
+
+struct Buffer<I> {
+    items: Vec<I>,
+    generation: u64,
+    timer: Option<Subscription<'static>>,
+    closed: bool,
+}
+
+struct BufferTime<I: 'static, E: 'static> {
+    buffer: Arc<Mutex<Buffer<I>>>,
+    observer: BaseObserver<'static, Vec<I>, E>,
+    scheduler: Scheduler,
+    span: Duration,
+    max: Option<usize>,
+}
+
+impl<I, E> Clone for BufferTime<I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            observer: self.observer.clone(),
+            scheduler: self.scheduler.clone(),
+            span: self.span,
+            max: self.max,
+        }
+    }
+}
+
+impl<I, E> BufferTime<I, E> where I: Send + Sync + 'static {
+    fn schedule(&self, generation: u64) {
+        let buffer_time = self.clone();
+        let timer = self.scheduler.run_after(self.span, move || buffer_time.flush(Some(generation)));
+        let stale = {
+            let mut buffer = self.buffer.lock().unwrap();
+            if !buffer.closed && buffer.generation == generation {
+                buffer.timer.replace(timer)
+            } else {
+                Some(timer)
+            }
+        };
+        if let Some(stale) = stale {
+            stale.unsubscribe()
+        }
+    }
+
+    /// Emits the current buffer and starts the next one, unless a newer buffer already replaced it.
+    fn flush(&self, generation: Option<u64>) {
+        let (items, timer, generation) = {
+            let mut buffer = self.buffer.lock().unwrap();
+            if buffer.closed || generation.is_some_and(|generation| generation != buffer.generation) {
+                return;
+            }
+            buffer.generation += 1;
+            (std::mem::take(&mut buffer.items), buffer.timer.take(), buffer.generation)
+        };
+        if let Some(timer) = timer {
+            timer.unsubscribe()
+        }
+        self.schedule(generation);
+        self.observer.on_next(items)
+    }
+
+    fn next(&self, item: I) {
+        let full = {
+            let mut buffer = self.buffer.lock().unwrap();
+            if buffer.closed {
+                return;
+            }
+            buffer.items.push(item);
+            self.max.is_some_and(|max| buffer.items.len() >= max)
+        };
+        if full {
+            self.flush(None)
+        }
+    }
+
+    fn close(&self) -> Vec<I> {
+        let (items, timer) = {
+            let mut buffer = self.buffer.lock().unwrap();
+            buffer.closed = true;
+            (std::mem::take(&mut buffer.items), buffer.timer.take())
+        };
+        if let Some(timer) = timer {
+            timer.unsubscribe()
+        }
+        items
+    }
+
+    fn complete(self) {
+        let items = self.close();
+        self.observer.on_next(items);
+        self.observer.on_completed()
+    }
+
+    fn error(self, error: E) {
+        self.close();
+        self.observer.on_error(error)
+    }
+}
+
+impl<O> Observable<'static> for BufferTimeObservable<O> where O: Observable<'static> + 'static, O::Item: Send + Sync {
+    type Item = Vec<O::Item>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let buffer_time = BufferTime {
+            buffer: Arc::new(Mutex::new(Buffer { items: Vec::new(), generation: 0, timer: None, closed: false })),
+            observer: BaseObserver::new(observer),
+            scheduler: self.scheduler,
+            span: self.span,
+            max: self.max,
+        };
+        let next = {
+            let buffer_time = buffer_time.clone();
+            move |item| buffer_time.next(item)
+        };
+        let complete = {
+            let buffer_time = buffer_time.clone();
+            move || buffer_time.complete()
+        };
+        let error = {
+            let buffer_time = buffer_time.clone();
+            move |error| buffer_time.error(error)
+        };
+        buffer_time.schedule(0);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            buffer_time.close();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+        let buffers = [('x', vec![1, 2]), ('y', vec![3]), ('z', vec![4]), ('e', vec![])];
+
+        let source = test.cold::<i32, ()>("-ab-c--d---|", &values);
+        test.expect_observable(source.buffer_time_on(Duration::from_millis(3), test.scheduler()))
+            .to_be("---x--y--z-(e|)", &buffers);
+
+        let source = test.cold::<i32, ()>("-a------|", &values);
+        test.expect_observable(source.buffer_time_on(Duration::from_millis(3), test.scheduler()))
+            .to_be("---x--e-(e|)", &[('x', vec![1]), ('e', vec![])]);
+
+        let source = test.cold::<i32, ()>("-a--|", &values);
+        test.expect_observable(source.buffer_time_on(Duration::from_millis(3), test.scheduler()))
+            .to_be("---x(e|)", &[('x', vec![1]), ('e', vec![])]);
+
+        let source = test.cold::<i32, ()>("-a---b|", &values);
+        test.expect_observable(source.buffer_time_on(Duration::from_millis(3), test.scheduler()))
+            .to_be("---x--(y|)", &[('x', vec![1]), ('y', vec![2])]);
+
+        let source = test.cold::<i32, ()>("-a-#", &values);
+        test.expect_observable(source.buffer_time_on(Duration::from_millis(5), test.scheduler()))
+            .to_be("---#", &buffers);
+    }
+
+    #[test]
+    fn buffer_time_or_count() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3)];
+        let buffers = [('x', vec![1, 2]), ('y', vec![3]), ('e', vec![])];
+        let source = test.cold::<i32, ()>("-abc-----|", &values);
+        test.expect_observable(source.buffer_time_or_count_on(Duration::from_millis(3), 2, test.scheduler()))
+            .to_be("--x--y--e(e|)", &buffers);
+    }
+}
@@ -8,7 +8,19 @@ pub struct CombineObservable<O, OO> {
     other: OO,
 }
 
+pub struct CombineVecObservable<O> {
+    observables: Vec<O>,
+}
+
+impl<O> CombineVecObservable<O> {
+    pub(crate) fn new(observables: Vec<O>) -> Self {
+        Self { observables }
+    }
+}
+
 pub trait CombineExt<'a>: Observable<'a> + Sized {
+    /// Emits the latest pair of items whenever either side emits, once both have emitted. Completes
+    /// once both sides have completed, or as soon as one completes without having emitted.
     fn combine<O>(self, other: O) -> CombineObservable<Self, O> where O: Observable<'a, Error=<Self as Observable<'a>>::Error> + 'a {
         CombineObservable { original: self, other }
     }
@@ -16,60 +28,75 @@ pub trait CombineExt<'a>: Observable<'a> + Sized {
 
 impl<'a, O> CombineExt<'a> for O where O: Observable<'a> {}
 
+struct Pair<A, B> {
+    left: Option<A>,
+    right: Option<B>,
+    completed: [bool; 2],
+}
+
+impl<A, B> Pair<A, B> {
+    fn complete(&mut self, index: usize) -> bool {
+        self.completed[index] = true;
+        let emitted = if index == 0 { self.left.is_some() } else { self.right.is_some() };
+        !emitted || self.completed.iter().all(|completed| *completed)
+    }
+}
+
 impl<'a, O, OO> Observable<'a> for CombineObservable<O, OO>
     where
         O: Observable<'a> + 'a,
         OO: Observable<'a, Error=O::Error> + 'a,
-        <O as Observable<'a>>::Item: Send + Sync,
-        <OO as Observable<'a>>::Item: Send + Sync,
+        <O as Observable<'a>>::Item: Clone + Send + Sync,
+        <OO as Observable<'a>>::Item: Clone + Send + Sync,
 {
     type Item = (O::Item, OO::Item);
     type Error = O::Error;
 
     fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
-        let item_1: Arc<Mutex<Option<O::Item>>> = Arc::new(Mutex::new(None));
-        let item_2: Arc<Mutex<Option<OO::Item>>> = Arc::new(Mutex::new(None));
+        let pair = Arc::new(Mutex::new(Pair { left: None, right: None, completed: [false; 2] }));
         let observer = BaseObserver::new(observer);
         let next_1 = {
-            let item_1 = item_1.clone();
-            let item_2 = item_2.clone();
+            let pair = pair.clone();
             let observer = observer.clone();
-            move |item| {
-                let item_2 = unsafe { std::mem::transmute_copy(&*item_2.lock().unwrap()) };
-                item_1.lock().unwrap().replace(unsafe { std::mem::transmute_copy(&item) });
-                if let Some(item_2) = item_2 {
-                    observer.on_next((item, item_2))
+            move |item: O::Item| {
+                let right = {
+                    let mut pair = pair.lock().unwrap();
+                    pair.left.replace(item.clone());
+                    pair.right.clone()
+                };
+                if let Some(right) = right {
+                    observer.on_next((item, right))
                 }
             }
         };
         let next_2 = {
-            let item_1 = item_1.clone();
-            let item_2 = item_2.clone();
+            let pair = pair.clone();
             let observer = observer.clone();
-            move |item| {
-                let item_1 = unsafe { std::mem::transmute_copy(&*item_1.lock().unwrap()) };
-                item_2.lock().unwrap().replace(unsafe { std::mem::transmute_copy(&item) });
-                if let Some(item_1) = item_1 {
-                    observer.on_next((item_1, item))
+            move |item: OO::Item| {
+                let left = {
+                    let mut pair = pair.lock().unwrap();
+                    pair.right.replace(item.clone());
+                    pair.left.clone()
+                };
+                if let Some(left) = left {
+                    observer.on_next((left, item))
                 }
             }
         };
-        let complete = {
-            let completed = Arc::new(Mutex::new(false));
+        let complete = |index: usize| {
+            let pair = pair.clone();
             let observer = observer.clone();
             move || {
-                if *&*completed.lock().unwrap() {
+                let done = pair.lock().unwrap().complete(index);
+                if done {
                     observer.on_completed()
-                } else {
-                    *completed.lock().unwrap() = true;
                 }
             }
         };
+        let (complete_1, complete_2) = (complete(0), complete(1));
         let error = move |error| observer.on_error(error);
-        let obs_1 = (next_1, error.clone(), complete.clone());
-        let obs_2 = (next_2, error, complete);
-        let sub1 = self.original.subscribe(obs_1);
-        let sub2 = self.other.subscribe(obs_2);
+        let sub1 = self.original.subscribe((next_1, error.clone(), complete_1));
+        let sub2 = self.other.subscribe((next_2, error, complete_2));
         Subscription::new(move || {
             sub1.unsubscribe();
             sub2.unsubscribe();
@@ -77,28 +104,77 @@ impl<'a, O, OO> Observable<'a> for CombineObservable<O, OO>
     }
 }
 
+struct Latest<I> {
+    items: Vec<Option<I>>,
+    remaining: usize,
+}
+
+impl<'a, O> Observable<'a> for CombineVecObservable<O> where O: Observable<'a> + 'a, O::Item: Clone + Send + Sync + 'a {
+    type Item = Vec<O::Item>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let count = self.observables.len();
+        if count == 0 {
+            observer.on_completed();
+            return Subscription::new(|| {});
+        }
+        let latest = Arc::new(Mutex::new(Latest { items: vec![None; count], remaining: count }));
+        let subs: Vec<_> = self.observables.into_iter().enumerate()
+            .map(|(index, observable)| {
+                let next = {
+                    let observer = observer.clone();
+                    let latest = latest.clone();
+                    move |item| {
+                        let row = {
+                            let mut latest = latest.lock().unwrap();
+                            latest.items[index] = Some(item);
+                            latest.items.iter().cloned().collect::<Option<Vec<_>>>()
+                        };
+                        if let Some(row) = row {
+                            observer.on_next(row)
+                        }
+                    }
+                };
+                let complete = {
+                    let observer = observer.clone();
+                    let latest = latest.clone();
+                    move || {
+                        // a source that completes without emitting means no combination can ever be emitted
+                        let done = {
+                            let mut latest = latest.lock().unwrap();
+                            latest.remaining -= 1;
+                            latest.remaining == 0 || latest.items[index].is_none()
+                        };
+                        if done {
+                            observer.on_completed()
+                        }
+                    }
+                };
+                let error = {
+                    let observer = observer.clone();
+                    move |error| observer.on_error(error)
+                };
+                observable.subscribe((next, error, complete))
+            })
+            .collect();
+        Subscription::new(move || subs.into_iter().for_each(|sub| sub.unsubscribe()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::sync::{Arc, Mutex};
     use crate::prelude::*;
-    use crate::Subject;
+    use crate::{factory, Subject};
+    use crate::testing::{MarbleTest, TestObserver};
 
     #[test]
     fn it_works() {
         let input = Subject::<i64, ()>::new();
         let input2 = Subject::<i64, ()>::new();
-        let data = Arc::new(Mutex::new(Vec::new()));
-        {
-            let data = data.clone();
-            let finish = data.clone();
-            input.fork()
-                .combine(input2.fork())
-                .subscribe((
-                    move |(x1, x2)| { data.lock().unwrap().push((x1, x2)); },
-                    move |_| {},
-                    move || { finish.lock().unwrap().push((10, 10)); }
-                ));
-        }
+        let observer = TestObserver::new();
+        input.fork().combine(input2.fork()).subscribe(observer.fork());
 
         input.on_next(1);
         input2.on_next(2);
@@ -106,8 +182,38 @@ mod tests {
         input2.on_next(1);
         input.on_next(2);
         input.on_completed();
+        observer.assert_values(&[(1, 2), (3, 2), (3, 1), (2, 1)]).assert_not_terminated();
+
+        input2.on_completed();
+        observer.assert_completed();
+    }
+
+    #[test]
+    fn completes_without_emitting() {
+        let input = Subject::<i64, ()>::new();
+        let input2 = Subject::<i64, ()>::new();
+        let observer = TestObserver::<(i64, i64), ()>::new();
+        input.fork().combine(input2.fork()).subscribe(observer.fork());
+
+        input.on_next(1);
         input2.on_completed();
+        observer.assert_values(&[]).assert_completed();
+    }
+
+    #[test]
+    fn combine_latest() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3)];
+        let sources = vec![
+            test.cold::<i32, ()>("-a---c-|", &values),
+            test.cold::<i32, ()>("--b|", &values),
+            test.cold::<i32, ()>("---a----|", &values),
+        ];
+        test.expect_observable(factory::combine_latest(sources))
+            .to_be("---x-y--|", &[('x', vec![1, 2, 1]), ('y', vec![3, 2, 1])]);
 
-        assert_eq!(&vec![(1, 2), (3, 2), (3, 1), (2, 1), (10, 10)], &*data.lock().unwrap());
+        let sources = vec![test.cold::<i32, ()>("-a---|", &values), test.cold::<i32, ()>("--|", &values)];
+        test.expect_observable(factory::combine_latest(sources))
+            .to_be("--|", &[]);
     }
 }
@@ -0,0 +1,142 @@
+use crate::extensions::flat_map::FlatMapExt;
+use crate::observable::{Observable, SubscriptionSlot};
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::Mutex;
+
+pub struct ConcatMapObservable<F, O> {
+    concat_map: F,
+    original: O,
+}
+
+pub struct ConcatObservable<O, OO> {
+    original: O,
+    other: OO,
+}
+
+pub trait ConcatExt<'a>: Observable<'a> + Sized {
+    /// Maps each item to an observable and subscribes to them one at a time, queueing items that
+    /// arrive while an inner observable is still running.
+    fn concat_map<F, OO>(self, concat_map: F) -> ConcatMapObservable<F, Self>
+        where OO: Observable<'a, Error=Self::Error> + 'a, F: Fn(Self::Item) -> OO + Send + Sync + 'a
+    {
+        ConcatMapObservable { concat_map, original: self }
+    }
+
+    /// Mirrors this observable, then `other` once this one completes.
+    fn concat<OO>(self, other: OO) -> ConcatObservable<Self, OO>
+        where OO: Observable<'a, Item=Self::Item, Error=Self::Error> + 'a
+    {
+        ConcatObservable { original: self, other }
+    }
+}
+
+impl<'a, O> ConcatExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, F, O, OO> Observable<'a> for ConcatMapObservable<F, O>
+    where O: Observable<'a, Error=OO::Error> + 'a,
+          OO: Observable<'a> + 'a,
+          F: Fn(O::Item) -> OO + Send + Sync + 'a,
+          O::Item: Send + Sync + 'a,
+{
+    type Item = OO::Item;
+    type Error = OO::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        self.original.and_then_limited(1, self.concat_map).subscribe(observer)
+    }
+}
+
+impl<'a, O, OO> Observable<'a> for ConcatObservable<O, OO>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Item=O::Item, Error=O::Error> + Send + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let second = SubscriptionSlot::new();
+        let next = {
+            let observer = observer.clone();
+            move |item| observer.on_next(item)
+        };
+        let complete = {
+            let observer = observer.clone();
+            let second = second.clone();
+            let other = Mutex::new(Some(self.other));
+            move || {
+                let other = other.lock().unwrap().take();
+                if let Some(other) = other {
+                    second.set(other.subscribe(observer.clone()))
+                }
+            }
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            second.cancel();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+    use crate::Subscription;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 0), ('b', 1), ('x', 10), ('y', 20), ('z', 30)];
+        let inners = [test.cold::<usize, ()>("-x-y|", &values), test.cold::<usize, ()>("-z|", &values)];
+        let second = inners[1].subscriptions();
+        let source = test.cold::<usize, ()>("-a--b|", &values);
+        test.expect_observable(source.concat_map(move |x| inners[x].fork()))
+            .to_be("--x-y-z|", &values);
+        test.expect_subscriptions(second).to_be(&["-----^-!"]);
+    }
+
+    #[test]
+    fn concat() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2)];
+        let first = test.cold::<i32, ()>("-a|", &values);
+        let second = test.cold::<i32, ()>("-b|", &values);
+        test.expect_observable(first.concat(second))
+            .to_be("-a-b|", &values);
+
+        let observer = TestObserver::<i32, ()>::new();
+        factory::concat(vec![factory::from_iter(vec![1, 2]), factory::from_iter(vec![3])]).subscribe(observer.fork());
+        observer.assert_values(&[1, 2, 3]).assert_completed();
+    }
+
+    #[test]
+    fn unsubscribe_while_switching() {
+        let test = MarbleTest::new();
+        let outer = Arc::new(Mutex::new(None::<Subscription<'static>>));
+        let late = Arc::new(Mutex::new(None));
+        let other = {
+            let outer = outer.clone();
+            let late = late.clone();
+            factory::create(move |sub| {
+                if let Some(outer) = outer.lock().unwrap().take() {
+                    outer.unsubscribe()
+                }
+                late.lock().unwrap().replace(sub);
+            })
+        };
+        let observer = TestObserver::<i32, ()>::new();
+        let source = test.cold::<i32, ()>("-a|", &[('a', 0)]);
+        let sub = source.concat(other).subscribe(observer.fork());
+        outer.lock().unwrap().replace(sub);
+        test.flush();
+
+        late.lock().unwrap().take().unwrap().on_next(7);
+        observer.assert_values(&[0]).assert_not_terminated();
+    }
+}
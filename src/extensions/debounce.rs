@@ -0,0 +1,259 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Scheduler, Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub struct DebounceObservable<O> {
+    duration: Duration,
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub struct DebounceWithObservable<S, O> {
+    selector: S,
+    original: O,
+}
+
+pub trait DebounceExt<'a>: Observable<'a> + Sized {
+    /// Emits an item only once `duration` has passed without a newer one.
+    fn debounce(self, duration: Duration) -> DebounceObservable<Self> {
+        self.debounce_on(duration, Scheduler::new_thread())
+    }
+
+    fn debounce_on(self, duration: Duration, scheduler: Scheduler) -> DebounceObservable<Self> {
+        DebounceObservable { duration, scheduler, original: self }
+    }
+
+    /// Like `debounce`, but the quiet window of each item lasts until the observable returned by
+    /// `selector` emits.
+    fn debounce_with<S, OO>(self, selector: S) -> DebounceWithObservable<S, Self>
+        where S: Fn(&Self::Item) -> OO + Send + Sync + 'a, OO: Observable<'a, Error=Self::Error> + 'a
+    {
+        DebounceWithObservable { selector, original: self }
+    }
+}
+
+impl<'a, O> DebounceExt<'a> for O where O: Observable<'a> {}
+
+struct Pending<'a, I> {
+    item: Option<I>,
+    generation: u64,
+    window: Option<Subscription<'a>>,
+}
+
+impl<'a, I> Pending<'a, I> {
+    fn new() -> Self {
+        Self { item: None, generation: 0, window: None }
+    }
+
+    /// Replaces the pending item, returning its generation and the window it supersedes.
+    fn replace(&mut self, item: I) -> (u64, Option<Subscription<'a>>) {
+        self.generation += 1;
+        self.item = Some(item);
+        (self.generation, self.window.take())
+    }
+
+    fn take(&mut self, generation: u64) -> Option<I> {
+        if self.generation == generation {
+            self.window = None;
+            self.item.take()
+        } else {
+            None
+        }
+    }
+
+    /// Keeps `window` if it still belongs to the pending item, otherwise hands it back.
+    fn track(&mut self, generation: u64, window: Subscription<'a>) -> Option<Subscription<'a>> {
+        if self.generation == generation && self.item.is_some() {
+            self.window.replace(window)
+        } else {
+            Some(window)
+        }
+    }
+
+    fn clear(&mut self) -> Option<Subscription<'a>> {
+        self.generation += 1;
+        self.item = None;
+        self.window.take()
+    }
+}
+
+impl<O> Observable<'static> for DebounceObservable<O> where O: Observable<'static> + 'static, O::Item: Send + Sync {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let duration = self.duration;
+        let scheduler = self.scheduler;
+        let pending = Arc::new(Mutex::new(Pending::new()));
+        let observer = BaseObserver::new(observer);
+        let next = {
+            let observer = observer.clone();
+            let pending = pending.clone();
+            move |item| {
+                let (generation, window) = pending.lock().unwrap().replace(item);
+                if let Some(window) = window {
+                    window.unsubscribe()
+                }
+                let timer = {
+                    let observer = observer.clone();
+                    let pending = pending.clone();
+                    scheduler.run_after(duration, move || {
+                        let item = pending.lock().unwrap().take(generation);
+                        if let Some(item) = item {
+                            observer.on_next(item)
+                        }
+                    })
+                };
+                let stale = pending.lock().unwrap().track(generation, timer);
+                if let Some(timer) = stale {
+                    timer.unsubscribe()
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let pending = pending.clone();
+            move || flush(&pending, observer)
+        };
+        let error = {
+            let pending = pending.clone();
+            move |error| {
+                let window = pending.lock().unwrap().clear();
+                if let Some(window) = window {
+                    window.unsubscribe()
+                }
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            let window = pending.lock().unwrap().clear();
+            if let Some(window) = window {
+                window.unsubscribe()
+            }
+        })
+    }
+}
+
+impl<'a, S, O, OO> Observable<'a> for DebounceWithObservable<S, O>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Error=O::Error> + 'a,
+          S: Fn(&O::Item) -> OO + Send + Sync + 'a,
+          O::Item: Send + Sync,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let selector = self.selector;
+        let pending = Arc::new(Mutex::new(Pending::new()));
+        let observer = BaseObserver::new(observer);
+        let next = {
+            let observer = observer.clone();
+            let pending = pending.clone();
+            move |item| {
+                let window = selector(&item);
+                let (generation, previous) = pending.lock().unwrap().replace(item);
+                if let Some(previous) = previous {
+                    previous.unsubscribe()
+                }
+                let window_next = {
+                    let observer = observer.clone();
+                    let pending = pending.clone();
+                    move |_| {
+                        let item = pending.lock().unwrap().take(generation);
+                        if let Some(item) = item {
+                            observer.on_next(item)
+                        }
+                    }
+                };
+                let window_error = {
+                    let observer = observer.clone();
+                    move |error| observer.on_error(error)
+                };
+                let window = window.subscribe((window_next, window_error, || {}));
+                let stale = pending.lock().unwrap().track(generation, window);
+                if let Some(window) = stale {
+                    window.unsubscribe()
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let pending = pending.clone();
+            move || flush(&pending, observer)
+        };
+        let error = {
+            let pending = pending.clone();
+            move |error| {
+                let window = pending.lock().unwrap().clear();
+                if let Some(window) = window {
+                    window.unsubscribe()
+                }
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            let window = pending.lock().unwrap().clear();
+            if let Some(window) = window {
+                window.unsubscribe()
+            }
+        })
+    }
+}
+
+fn flush<'a, I, E>(pending: &Mutex<Pending<'a, I>>, observer: BaseObserver<'a, I, E>) {
+    let (item, window) = {
+        let mut pending = pending.lock().unwrap();
+        let item = pending.item.take();
+        (item, pending.clear())
+    };
+    if let Some(window) = window {
+        window.unsubscribe()
+    }
+    if let Some(item) = item {
+        observer.on_next(item)
+    }
+    observer.on_completed()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::MarbleTest;
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+        let source = test.cold::<i32, ()>("-a-----bc-----d-|", &values);
+        test.expect_observable(source.debounce_on(Duration::from_millis(3), test.scheduler()))
+            .to_be("----a------c----(d|)", &values);
+
+        let source = test.cold::<i32, ()>("-a-#", &values);
+        test.expect_observable(source.debounce_on(Duration::from_millis(3), test.scheduler()))
+            .to_be("---#", &values);
+    }
+
+    #[test]
+    fn debounce_with() {
+        let test = MarbleTest::new();
+        let values = [('a', 2), ('b', 5), ('c', 1)];
+        let scheduler = test.scheduler();
+        let source = test.cold::<u64, ()>("-a------b-|", &values);
+        test.expect_observable(source.debounce_with(move |x| factory::timer_on(Duration::from_millis(*x), scheduler.clone())))
+            .to_be("---a------(b|)", &values);
+
+        let scheduler = test.scheduler();
+        let source = test.cold::<u64, ()>("-b--c----|", &values);
+        test.expect_observable(source.debounce_with(move |x| factory::timer_on(Duration::from_millis(*x), scheduler.clone())))
+            .to_be("-----c---|", &values);
+    }
+}
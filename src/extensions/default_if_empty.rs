@@ -0,0 +1,158 @@
+use crate::observable::{Observable, SubscriptionSlot};
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub struct DefaultIfEmptyObservable<O, I> {
+    default: I,
+    original: O,
+}
+
+pub struct SwitchIfEmptyObservable<O, OO> {
+    other: OO,
+    original: O,
+}
+
+pub trait DefaultIfEmptyExt<'a>: Observable<'a> + Sized {
+    /// Emits `default` if the source completes without having emitted anything.
+    fn default_if_empty(self, default: Self::Item) -> DefaultIfEmptyObservable<Self, Self::Item> {
+        DefaultIfEmptyObservable { default, original: self }
+    }
+
+    /// Switches to `other` if the source completes without having emitted anything.
+    fn switch_if_empty<OO>(self, other: OO) -> SwitchIfEmptyObservable<Self, OO>
+        where OO: Observable<'a, Item=Self::Item, Error=Self::Error> + 'a
+    {
+        SwitchIfEmptyObservable { other, original: self }
+    }
+}
+
+impl<'a, O> DefaultIfEmptyExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, O> Observable<'a> for DefaultIfEmptyObservable<O, O::Item> where O: Observable<'a> + 'a, O::Item: Send + Sync + 'a {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let default = self.default;
+        let observer = BaseObserver::new(observer);
+        let emitted = Arc::new(AtomicBool::new(false));
+        let next = {
+            let observer = observer.clone();
+            let emitted = emitted.clone();
+            move |item| {
+                emitted.store(true, Ordering::SeqCst);
+                observer.on_next(item)
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || {
+                if !emitted.load(Ordering::SeqCst) {
+                    observer.on_next(default)
+                }
+                observer.on_completed()
+            }
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+impl<'a, O, OO> Observable<'a> for SwitchIfEmptyObservable<O, OO>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Item=O::Item, Error=O::Error> + Send + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let emitted = Arc::new(AtomicBool::new(false));
+        let fallback = SubscriptionSlot::new();
+        let next = {
+            let observer = observer.clone();
+            let emitted = emitted.clone();
+            move |item| {
+                emitted.store(true, Ordering::SeqCst);
+                observer.on_next(item)
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let fallback = fallback.clone();
+            let other = Mutex::new(Some(self.other));
+            move || {
+                let other = other.lock().unwrap().take();
+                match other {
+                    Some(other) if !emitted.load(Ordering::SeqCst) => fallback.set(other.subscribe(observer)),
+                    _ => observer.on_completed(),
+                }
+            }
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            fallback.cancel();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+    use crate::Subscription;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn it_works() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::empty().default_if_empty(7).subscribe(observer.fork());
+        observer.assert_values(&[7]).assert_completed();
+
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2]).default_if_empty(7).subscribe(observer.fork());
+        observer.assert_values(&[1, 2]).assert_completed();
+    }
+
+    #[test]
+    fn switch_if_empty() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::empty().switch_if_empty(factory::from_iter(vec![8, 9])).subscribe(observer.fork());
+        observer.assert_values(&[8, 9]).assert_completed();
+
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_value(1).switch_if_empty(factory::from_value(8)).subscribe(observer.fork());
+        observer.assert_values(&[1]).assert_completed();
+    }
+
+    #[test]
+    fn unsubscribe_while_switching() {
+        let test = MarbleTest::new();
+        let outer = Arc::new(Mutex::new(None::<Subscription<'static>>));
+        let late = Arc::new(Mutex::new(None));
+        let fallback = {
+            let outer = outer.clone();
+            let late = late.clone();
+            factory::create(move |sub| {
+                if let Some(outer) = outer.lock().unwrap().take() {
+                    outer.unsubscribe()
+                }
+                late.lock().unwrap().replace(sub);
+            })
+        };
+        let observer = TestObserver::<i32, ()>::new();
+        let source = test.cold::<i32, ()>("--|", &[]);
+        let sub = source.switch_if_empty(fallback).subscribe(observer.fork());
+        outer.lock().unwrap().replace(sub);
+        test.flush();
+
+        late.lock().unwrap().take().unwrap().on_next(7);
+        observer.assert_values(&[]).assert_not_terminated();
+    }
+}
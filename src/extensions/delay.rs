@@ -0,0 +1,357 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::extensions::thread::{drain, Mailbox};
+use crate::{Scheduler, Subscription, BaseObserver, Notification};
+use std::convert::Infallible;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub struct DelayObservable<O> {
+    duration: Duration,
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub struct DelayWhenObservable<S, O> {
+    selector: S,
+    original: O,
+}
+
+pub trait DelayExt<'a>: Observable<'a> + Sized {
+    /// Shifts every item and the completion by `duration`. Errors are forwarded immediately.
+    fn delay(self, duration: Duration) -> DelayObservable<Self> {
+        self.delay_on(duration, Scheduler::new_thread())
+    }
+
+    fn delay_on(self, duration: Duration, scheduler: Scheduler) -> DelayObservable<Self> {
+        DelayObservable { duration, scheduler, original: self }
+    }
+
+    /// Holds each item until the observable returned by `selector` emits or completes. Items keep
+    /// their order, so an item whose delay is over still waits for the ones before it.
+    fn delay_when<S, OO>(self, selector: S) -> DelayWhenObservable<S, Self>
+        where S: Fn(&Self::Item) -> OO + Send + Sync + 'a, OO: Observable<'a, Error=Self::Error> + 'a
+    {
+        DelayWhenObservable { selector, original: self }
+    }
+}
+
+impl<'a, O> DelayExt<'a> for O where O: Observable<'a> {}
+
+struct Delayed<I> {
+    sequence: u64,
+    queue: VecDeque<(u64, I)>,
+    timers: HashMap<u64, Subscription<'static>>,
+    completed: bool,
+}
+
+impl<I> Delayed<I> {
+    fn clear(&mut self) {
+        self.queue.clear();
+        self.timers.drain().for_each(|(_, timer)| timer.unsubscribe());
+    }
+}
+
+impl<O> Observable<'static> for DelayObservable<O> where O: Observable<'static> + 'static, O::Item: Send + Sync {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let duration = self.duration;
+        let scheduler = self.scheduler;
+        let observer = BaseObserver::new(observer);
+        let delayed = Arc::new(Mutex::new(Delayed { sequence: 0, queue: VecDeque::new(), timers: HashMap::new(), completed: false }));
+        // timers may fire on different threads, so released items are posted in queue order and
+        // delivered by a single drain loop
+        let mailbox = Arc::new(Mutex::new(Mailbox::<_, Infallible>::new()));
+        let next = {
+            let observer = observer.clone();
+            let delayed = delayed.clone();
+            let mailbox = mailbox.clone();
+            move |item| {
+                let id = {
+                    let mut delayed = delayed.lock().unwrap();
+                    let id = delayed.sequence;
+                    delayed.sequence += 1;
+                    delayed.queue.push_back((id, item));
+                    id
+                };
+                let timer = {
+                    let observer = observer.clone();
+                    let delayed = delayed.clone();
+                    let mailbox = mailbox.clone();
+                    // every item waits the same time, so whichever timer fires releases the oldest item
+                    scheduler.run_after(duration, move || {
+                        let must_drain = {
+                            let mut delayed = delayed.lock().unwrap();
+                            let (id, item) = match delayed.queue.pop_front() {
+                                Some(entry) => entry,
+                                None => return,
+                            };
+                            delayed.timers.remove(&id);
+                            let mut mailbox = mailbox.lock().unwrap();
+                            let must_drain = mailbox.post(Notification::Next(item));
+                            if delayed.completed && delayed.queue.is_empty() {
+                                mailbox.post(Notification::Completed);
+                            }
+                            must_drain
+                        };
+                        if must_drain {
+                            drain(&mailbox, |notification| deliver(&observer, notification))
+                        }
+                    })
+                };
+                let mut delayed = delayed.lock().unwrap();
+                if delayed.queue.front().is_some_and(|(front, _)| *front <= id) {
+                    delayed.timers.insert(id, timer);
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let delayed = delayed.clone();
+            move || {
+                let must_drain = {
+                    let mut delayed = delayed.lock().unwrap();
+                    delayed.completed = true;
+                    delayed.queue.is_empty() && mailbox.lock().unwrap().post(Notification::Completed)
+                };
+                if must_drain {
+                    drain(&mailbox, |notification| deliver(&observer, notification))
+                }
+            }
+        };
+        let error = {
+            let delayed = delayed.clone();
+            move |error| {
+                delayed.lock().unwrap().clear();
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            delayed.lock().unwrap().clear();
+        })
+    }
+}
+
+struct Entry<'a, I> {
+    item: I,
+    ready: bool,
+    notifier: Option<Subscription<'a>>,
+}
+
+struct Pending<'a, I> {
+    front: u64,
+    entries: VecDeque<Entry<'a, I>>,
+    completed: bool,
+}
+
+impl<'a, I> Pending<'a, I> {
+    fn entry(&mut self, id: u64) -> Option<&mut Entry<'a, I>> {
+        let front = self.front;
+        id.checked_sub(front).and_then(move |index| self.entries.get_mut(index as usize))
+    }
+
+    /// Marks `id` ready and pops every ready item at the front of the queue.
+    fn release(&mut self, id: u64) -> (Option<Subscription<'a>>, Vec<I>) {
+        let notifier = match self.entry(id) {
+            Some(entry) if !entry.ready => {
+                entry.ready = true;
+                entry.notifier.take()
+            }
+            _ => return (None, Vec::new()),
+        };
+        let mut items = Vec::new();
+        while self.entries.front().is_some_and(|entry| entry.ready) {
+            items.extend(self.entries.pop_front().map(|entry| entry.item));
+            self.front += 1;
+        }
+        (notifier, items)
+    }
+
+    fn clear(&mut self) -> Vec<Subscription<'a>> {
+        self.front += self.entries.len() as u64;
+        self.entries.drain(..).filter_map(|entry| entry.notifier).collect()
+    }
+}
+
+impl<'a, S, O, OO> Observable<'a> for DelayWhenObservable<S, O>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Error=O::Error> + 'a,
+          S: Fn(&O::Item) -> OO + Send + Sync + 'a,
+          O::Item: Send + Sync,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let selector = self.selector;
+        let observer = BaseObserver::new(observer);
+        let pending = Arc::new(Mutex::new(Pending { front: 0, entries: VecDeque::new(), completed: false }));
+        let mailbox = Arc::new(Mutex::new(Mailbox::<_, Infallible>::new()));
+        let release = {
+            let observer = observer.clone();
+            let pending = pending.clone();
+            let mailbox = mailbox.clone();
+            move |id| {
+                let (notifier, must_drain) = {
+                    let mut pending = pending.lock().unwrap();
+                    let (notifier, items) = pending.release(id);
+                    let done = !items.is_empty() && pending.completed && pending.entries.is_empty();
+                    let mut mailbox = mailbox.lock().unwrap();
+                    let mut must_drain = false;
+                    for item in items {
+                        must_drain |= mailbox.post(Notification::Next(item));
+                    }
+                    if done {
+                        must_drain |= mailbox.post(Notification::Completed);
+                    }
+                    (notifier, must_drain)
+                };
+                if let Some(notifier) = notifier {
+                    notifier.unsubscribe()
+                }
+                if must_drain {
+                    drain(&mailbox, |notification| deliver(&observer, notification))
+                }
+            }
+        };
+        let clear = {
+            let pending = pending.clone();
+            move || {
+                let notifiers = pending.lock().unwrap().clear();
+                notifiers.into_iter().for_each(|notifier| notifier.unsubscribe());
+            }
+        };
+        let next = {
+            let observer = observer.clone();
+            let pending = pending.clone();
+            let clear = clear.clone();
+            move |item| {
+                let notifier = selector(&item);
+                let id = {
+                    let mut pending = pending.lock().unwrap();
+                    pending.entries.push_back(Entry { item, ready: false, notifier: None });
+                    pending.front + pending.entries.len() as u64 - 1
+                };
+                let notifier_next = {
+                    let release = release.clone();
+                    move |_| release(id)
+                };
+                let notifier_complete = {
+                    let release = release.clone();
+                    move || release(id)
+                };
+                let notifier_error = {
+                    let observer = observer.clone();
+                    let clear = clear.clone();
+                    move |error| {
+                        clear();
+                        observer.on_error(error)
+                    }
+                };
+                let sub = notifier.subscribe((notifier_next, notifier_error, notifier_complete));
+                let mut pending = pending.lock().unwrap();
+                match pending.entry(id) {
+                    Some(entry) if !entry.ready => {
+                        entry.notifier = Some(sub);
+                    }
+                    _ => {
+                        drop(pending);
+                        sub.unsubscribe()
+                    }
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let pending = pending.clone();
+            move || {
+                let must_drain = {
+                    let mut pending = pending.lock().unwrap();
+                    pending.completed = true;
+                    pending.entries.is_empty() && mailbox.lock().unwrap().post(Notification::Completed)
+                };
+                if must_drain {
+                    drain(&mailbox, |notification| deliver(&observer, notification))
+                }
+            }
+        };
+        let error = {
+            let clear = clear.clone();
+            move |error| {
+                clear();
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            clear();
+        })
+    }
+}
+
+/// Errors skip the mailbox and are forwarded as soon as they arrive.
+fn deliver<I, E>(observer: &BaseObserver<I, E>, notification: Notification<I, Infallible>) {
+    match notification {
+        Notification::Next(item) => observer.on_next(item),
+        Notification::Error(never) => match never {},
+        Notification::Completed => observer.clone().on_completed(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2)];
+
+        let source = test.cold::<i32, ()>("-a-b-|", &values);
+        test.expect_observable(source.delay_on(Duration::from_millis(2), test.scheduler()))
+            .to_be("---a-(b|)", &values);
+
+        let source = test.cold::<i32, ()>("-a-#", &values);
+        test.expect_observable(source.delay_on(Duration::from_millis(2), test.scheduler()))
+            .to_be("---#", &values);
+    }
+
+    #[test]
+    fn delay_when() {
+        let test = MarbleTest::new();
+        let values = [('a', 3), ('b', 1), ('c', 0)];
+        let scheduler = test.scheduler();
+        let source = test.cold::<u64, ()>("-ab-----c-|", &values);
+        test.expect_observable(source.delay_when(move |x| factory::timer_on(Duration::from_millis(*x), scheduler.clone())))
+            .to_be("----(ab)c-|", &values);
+
+        let source = test.cold::<u64, ()>("-a-|", &values);
+        test.expect_observable(source.delay_when(|_| factory::empty::<(), ()>()))
+            .to_be("-a-|", &values);
+    }
+
+    #[test]
+    fn keeps_order_across_timer_threads() {
+        let items: Vec<_> = (0..300).collect();
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(items.clone()).delay(Duration::from_millis(1)).subscribe(observer.fork());
+        assert!(observer.await_terminal(Duration::from_secs(5)));
+        observer.assert_values(&items).assert_completed();
+
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(items.clone())
+            .delay_when(|_| factory::timer(Duration::from_millis(1)))
+            .subscribe(observer.fork());
+        assert!(observer.await_terminal(Duration::from_secs(5)));
+        observer.assert_values(&items).assert_completed();
+    }
+}
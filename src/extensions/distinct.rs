@@ -0,0 +1,178 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Scheduler, Subscription, BaseObserver};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Limits how many keys `distinct_by_key_bounded` remembers. A forgotten key is let through again.
+#[derive(Clone)]
+pub enum DistinctBound {
+    /// Remembers at most this many keys, forgetting the least recently seen one first.
+    Lru(usize),
+    /// Forgets a key once this much time has passed on the scheduler's clock since it was let through.
+    Expire(Duration, Scheduler),
+}
+
+/// Key selector used by `distinct`, which keys each item by a clone of itself.
+pub type Identity<I> = fn(&I) -> I;
+
+pub struct DistinctObservable<F, O> {
+    key: F,
+    bound: Option<DistinctBound>,
+    original: O,
+}
+
+pub trait DistinctExt<'a>: Observable<'a> + Sized {
+    /// Drops every item equal to one emitted before.
+    fn distinct(self) -> DistinctObservable<Identity<Self::Item>, Self> where Self::Item: Hash + Eq + Clone {
+        self.distinct_by_key(Clone::clone)
+    }
+
+    /// Drops every item whose key was seen before.
+    fn distinct_by_key<F, K>(self, key: F) -> DistinctObservable<F, Self>
+        where F: Fn(&Self::Item) -> K + Send + Sync + 'a, K: Hash + Eq + Clone
+    {
+        DistinctObservable { key, bound: None, original: self }
+    }
+
+    /// Like `distinct_by_key`, but only remembers the keys allowed by `bound`.
+    fn distinct_by_key_bounded<F, K>(self, key: F, bound: DistinctBound) -> DistinctObservable<F, Self>
+        where F: Fn(&Self::Item) -> K + Send + Sync + 'a, K: Hash + Eq + Clone
+    {
+        DistinctObservable { key, bound: Some(bound), original: self }
+    }
+}
+
+impl<'a, O> DistinctExt<'a> for O where O: Observable<'a> {}
+
+struct Seen<K> {
+    bound: Option<DistinctBound>,
+    stamp: u64,
+    keys: HashMap<K, u64>,
+    order: VecDeque<(K, u64, Duration)>,
+}
+
+impl<K> Seen<K> where K: Hash + Eq + Clone {
+    /// Records `key`, returning whether it was new.
+    fn insert(&mut self, key: K) -> bool {
+        self.stamp += 1;
+        let stamp = self.stamp;
+        match self.bound.clone() {
+            None => self.keys.insert(key, stamp).is_none(),
+            Some(DistinctBound::Lru(capacity)) => {
+                let new = self.keys.insert(key.clone(), stamp).is_none();
+                self.order.push_back((key, stamp, Duration::from_secs(0)));
+                while self.keys.len() > capacity {
+                    self.evict_front();
+                }
+                // a key seen again leaves its older entry behind in `order`
+                if self.order.len() > 2 * self.keys.len() + 16 {
+                    let keys = &self.keys;
+                    self.order.retain(|(key, stamp, _)| keys.get(key) == Some(stamp));
+                }
+                new
+            }
+            Some(DistinctBound::Expire(ttl, scheduler)) => {
+                let now = scheduler.now();
+                while self.order.front().is_some_and(|(_, _, time)| *time + ttl <= now) {
+                    self.evict_front();
+                }
+                if self.keys.contains_key(&key) {
+                    return false;
+                }
+                self.keys.insert(key.clone(), stamp);
+                self.order.push_back((key, stamp, now));
+                true
+            }
+        }
+    }
+
+    fn evict_front(&mut self) {
+        if let Some((key, stamp, _)) = self.order.pop_front() {
+            if self.keys.get(&key) == Some(&stamp) {
+                self.keys.remove(&key);
+            }
+        }
+    }
+}
+
+impl<'a, F, K, O> Observable<'a> for DistinctObservable<F, O>
+    where O: Observable<'a> + 'a,
+          F: Fn(&O::Item) -> K + Send + Sync + 'a,
+          K: Hash + Eq + Clone + Send + Sync + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let key = self.key;
+        let seen = Arc::new(Mutex::new(Seen { bound: self.bound, stamp: 0, keys: HashMap::new(), order: VecDeque::new() }));
+        let observer = BaseObserver::new(observer);
+        let next = {
+            let observer = observer.clone();
+            move |item| {
+                let new = seen.lock().unwrap().insert(key(&item));
+                if new {
+                    observer.on_next(item)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || observer.on_completed()
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::{factory, DistinctBound, Subject, TestScheduler};
+    use crate::testing::TestObserver;
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2, 1, 3, 2, 4]).distinct().subscribe(observer.fork());
+        observer.assert_values(&[1, 2, 3, 4]).assert_completed();
+
+        let observer = TestObserver::<String, ()>::new();
+        factory::from_iter(vec!["a", "B", "A", "b", "c"].into_iter().map(String::from))
+            .distinct_by_key(|x| x.to_lowercase())
+            .subscribe(observer.fork());
+        observer.assert_values(&["a".to_string(), "B".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn lru() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2, 1, 3, 2, 1])
+            .distinct_by_key_bounded(|x| *x, DistinctBound::Lru(2))
+            .subscribe(observer.fork());
+        observer.assert_values(&[1, 2, 3, 2, 1]).assert_completed();
+    }
+
+    #[test]
+    fn expire() {
+        let test = TestScheduler::new();
+        let input = Subject::<i32, ()>::new();
+        let observer = TestObserver::<i32, ()>::new();
+        input.fork()
+            .distinct_by_key_bounded(|x| *x, DistinctBound::Expire(Duration::from_millis(10), test.scheduler()))
+            .subscribe(observer.fork());
+        input.on_next(1);
+        test.advance_by(Duration::from_millis(5));
+        input.on_next(1);
+        input.on_next(2);
+        test.advance_by(Duration::from_millis(5));
+        input.on_next(1);
+        input.on_next(2);
+        observer.assert_values(&[1, 2, 1]);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+
+/// Comparison used by `distinct_until_changed`.
+pub type Equality<I> = fn(&I, &I) -> bool;
+
+pub struct DistinctUntilChangedObservable<F, O> {
+    eq: F,
+    original: O,
+}
+
+pub trait DistinctUntilChangedExt<'a>: Observable<'a> + Sized {
+    /// Drops items equal to the one right before them.
+    fn distinct_until_changed(self) -> DistinctUntilChangedObservable<Equality<Self::Item>, Self>
+        where Self::Item: PartialEq + Clone
+    {
+        self.distinct_until_changed_by(PartialEq::eq)
+    }
+
+    /// Drops items for which `eq(previous, item)` holds.
+    fn distinct_until_changed_by<F>(self, eq: F) -> DistinctUntilChangedObservable<F, Self>
+        where F: Fn(&Self::Item, &Self::Item) -> bool + Send + Sync + 'a, Self::Item: Clone
+    {
+        DistinctUntilChangedObservable { eq, original: self }
+    }
+}
+
+impl<'a, O> DistinctUntilChangedExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, F, O> Observable<'a> for DistinctUntilChangedObservable<F, O>
+    where O: Observable<'a> + 'a,
+          F: Fn(&O::Item, &O::Item) -> bool + Send + Sync + 'a,
+          O::Item: Clone + Send + Sync + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let eq = self.eq;
+        let previous: Arc<Mutex<Option<O::Item>>> = Arc::new(Mutex::new(None));
+        let observer = BaseObserver::new(observer);
+        let next = {
+            let observer = observer.clone();
+            move |item: O::Item| {
+                let changed = {
+                    let mut previous = previous.lock().unwrap();
+                    let changed = previous.as_ref().is_none_or(|previous| !eq(previous, &item));
+                    if changed {
+                        previous.replace(item.clone());
+                    }
+                    changed
+                };
+                if changed {
+                    observer.on_next(item)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || observer.on_completed()
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::TestObserver;
+
+    #[test]
+    fn it_works() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 1, 2, 2, 1, 3, 3]).distinct_until_changed().subscribe(observer.fork());
+        observer.assert_values(&[1, 2, 1, 3]).assert_completed();
+    }
+
+    #[test]
+    fn distinct_until_changed_by() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2, 4, 5, 9])
+            .distinct_until_changed_by(|a: &i32, b: &i32| (a - b).abs() <= 1)
+            .subscribe(observer.fork());
+        observer.assert_values(&[1, 4, 9]).assert_completed();
+    }
+}
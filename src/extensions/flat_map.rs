@@ -1,19 +1,91 @@
-use crate::observable::Observable;
+use crate::observable::{Observable, SubscriptionSlot};
 use crate::observer::Observer;
 use crate::{Subscription, BaseObserver};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Mutex, Arc};
-use std::time::SystemTime;
+
+/// Identity mapping used by the `*_all` operators to flatten an observable of observables.
+pub type Flatten<I> = fn(I) -> I;
 
 pub struct FlatMapObservable<FM, O> {
     and_then: FM,
     original: O,
 }
 
+/// What `and_then_limited` does with an item that arrives while its pending queue is full.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverflowStrategy {
+    DropNewest,
+    DropOldest,
+}
+
+/// Live view of the items `and_then_limited` has buffered but not subscribed to yet.
+#[derive(Clone, Default)]
+pub struct PendingItems {
+    len: Arc<AtomicUsize>,
+}
+
+impl PendingItems {
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub struct FlatMapLimitedObservable<FM, O> {
+    and_then: FM,
+    max_concurrent: usize,
+    overflow: Option<(usize, OverflowStrategy)>,
+    pending: PendingItems,
+    original: O,
+}
+
+impl<FM, O> FlatMapLimitedObservable<FM, O> {
+    pub fn pending(&self) -> PendingItems {
+        self.pending.clone()
+    }
+
+    /// Holds at most `capacity` pending items, resolving overflow with `strategy`.
+    pub fn with_overflow(self, capacity: usize, strategy: OverflowStrategy) -> Self {
+        Self { overflow: Some((capacity, strategy)), ..self }
+    }
+}
+
+pub struct ExhaustMapObservable<FM, O> {
+    exhaust_map: FM,
+    original: O,
+}
+
 pub trait FlatMapExt<'a>: Observable<'a> + Sized {
     fn and_then<FM, OO>(self, and_then: FM) -> FlatMapObservable<FM, Self> where OO: Observable<'a> + 'a, FM: Fn(Self::Item) -> OO + Send + Sync + 'a {
         FlatMapObservable { and_then, original: self }
     }
+
+    /// Like `and_then`, but keeps at most `max_concurrent` inner observables subscribed, buffering
+    /// the items that arrive in the meantime. The buffer is unbounded unless `with_overflow` is set.
+    fn and_then_limited<FM, OO>(self, max_concurrent: usize, and_then: FM) -> FlatMapLimitedObservable<FM, Self>
+        where OO: Observable<'a> + 'a, FM: Fn(Self::Item) -> OO + Send + Sync + 'a
+    {
+        assert!(max_concurrent > 0, "max_concurrent must be positive");
+        FlatMapLimitedObservable { and_then, max_concurrent, overflow: None, pending: PendingItems::default(), original: self }
+    }
+
+    fn merge_map<FM, OO>(self, max_concurrent: usize, merge_map: FM) -> FlatMapLimitedObservable<FM, Self>
+        where OO: Observable<'a> + 'a, FM: Fn(Self::Item) -> OO + Send + Sync + 'a
+    {
+        self.and_then_limited(max_concurrent, merge_map)
+    }
+
+    /// Like `and_then`, but drops the items that arrive while an inner observable is still running.
+    fn exhaust_map<FM, OO>(self, exhaust_map: FM) -> ExhaustMapObservable<FM, Self>
+        where OO: Observable<'a> + 'a, FM: Fn(Self::Item) -> OO + Send + Sync + 'a
+    {
+        ExhaustMapObservable { exhaust_map, original: self }
+    }
 }
 
 impl<'a, O> FlatMapExt<'a> for O where O: Observable<'a> {}
@@ -31,12 +103,17 @@ impl<'a, FM, O, OO> Observable<'a> for FlatMapObservable<FM, O>
         let observer = BaseObserver::new(observer);
         let completed = Arc::new(Mutex::new(false));
         let subs = Arc::new(Mutex::new(HashMap::new()));
+        let next_id = Mutex::new(0u64);
         let next = {
             let observer = observer.clone();
             let completed = completed.clone();
             let subs = subs.clone();
             move |item| {
-                let id = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().subsec_nanos();
+                let id = {
+                    let mut next_id = next_id.lock().unwrap();
+                    *next_id += 1;
+                    *next_id
+                };
                 let observable = and_then(item);
                 let observer = observer.clone();
                 let next = {
@@ -48,16 +125,30 @@ impl<'a, FM, O, OO> Observable<'a> for FlatMapObservable<FM, O>
                     let completed = completed.clone();
                     let subs = subs.clone();
                     move || {
-                        let mut subs = subs.lock().unwrap();
-                        subs.remove(&id);
-                        if *completed.lock().unwrap() && subs.len() == 0 {
+                        let done = {
+                            let mut subs = subs.lock().unwrap();
+                            subs.remove(&id);
+                            *completed.lock().unwrap() && subs.is_empty()
+                        };
+                        if done {
                             observer.on_completed()
                         }
                     }
                 };
                 let error = move |error| observer.on_error(error);
+                // reserve the slot first, so an inner observable completing synchronously is not re-added
+                subs.lock().unwrap().insert(id, None);
                 let sub = observable.subscribe((next, error, complete));
-                subs.lock().unwrap().insert(id, sub);
+                let mut subs = subs.lock().unwrap();
+                match subs.get_mut(&id) {
+                    Some(slot) => {
+                        slot.replace(sub);
+                    }
+                    None => {
+                        drop(subs);
+                        sub.unsubscribe()
+                    }
+                }
             }
         };
         let complete = {
@@ -65,7 +156,7 @@ impl<'a, FM, O, OO> Observable<'a> for FlatMapObservable<FM, O>
             let subs = subs.clone();
             move || {
                 *completed.lock().unwrap() = true;
-                if subs.lock().unwrap().len() == 0 {
+                if subs.lock().unwrap().is_empty() {
                     observer.on_completed()
                 }
             }
@@ -73,71 +164,434 @@ impl<'a, FM, O, OO> Observable<'a> for FlatMapObservable<FM, O>
         let error = move |error| observer.on_error(error);
         let sub = self.original.subscribe((next, error, complete));
         Subscription::new(move || {
-            subs.lock().unwrap().drain().into_iter().for_each(move |(_, sub)| sub.unsubscribe());
+            let subs: Vec<_> = subs.lock().unwrap().drain().filter_map(|(_, sub)| sub).collect();
+            subs.into_iter().for_each(|sub| sub.unsubscribe());
             sub.unsubscribe();
         })
     }
 }
 
+struct Queue<'a, I> {
+    items: VecDeque<I>,
+    running: bool,
+    next_id: u64,
+    inners: HashMap<u64, Option<Subscription<'a>>>,
+    outer_completed: bool,
+    closed: bool,
+}
+
+struct FlatMapLimited<'a, FM, I, T, E> {
+    queue: Arc<Mutex<Queue<'a, I>>>,
+    outer: SubscriptionSlot<'a>,
+    observer: BaseObserver<'a, T, E>,
+    and_then: Arc<FM>,
+    max_concurrent: usize,
+    overflow: Option<(usize, OverflowStrategy)>,
+    pending: PendingItems,
+}
+
+impl<'a, FM, I, T, E> Clone for FlatMapLimited<'a, FM, I, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+            outer: self.outer.clone(),
+            observer: self.observer.clone(),
+            and_then: self.and_then.clone(),
+            max_concurrent: self.max_concurrent,
+            overflow: self.overflow,
+            pending: self.pending.clone(),
+        }
+    }
+}
+
+impl<'a, FM, I, OO> FlatMapLimited<'a, FM, I, OO::Item, OO::Error>
+    where FM: Fn(I) -> OO + Send + Sync + 'a,
+          OO: Observable<'a> + 'a,
+          I: Send + Sync + 'a,
+{
+    fn next(&self, item: I) {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            if queue.closed {
+                return;
+            }
+            let full = queue.inners.len() >= self.max_concurrent;
+            match self.overflow {
+                Some((capacity, OverflowStrategy::DropNewest)) if full && queue.items.len() >= capacity => return,
+                Some((capacity, OverflowStrategy::DropOldest)) if full && queue.items.len() >= capacity => {
+                    queue.items.pop_front();
+                    if capacity > 0 {
+                        queue.items.push_back(item);
+                    }
+                }
+                _ => queue.items.push_back(item),
+            }
+            self.pending.len.store(queue.items.len(), Ordering::SeqCst);
+        }
+        self.run()
+    }
+
+    /// Subscribes to pending items while there are free slots. Inner observables that complete
+    /// synchronously are picked up by the loop instead of recursing.
+    fn run(&self) {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            if queue.running {
+                return;
+            }
+            queue.running = true;
+        }
+        loop {
+            let (id, item) = {
+                let mut queue = self.queue.lock().unwrap();
+                if queue.closed || queue.inners.len() >= self.max_concurrent {
+                    queue.running = false;
+                    return;
+                }
+                match queue.items.pop_front() {
+                    Some(item) => {
+                        self.pending.len.store(queue.items.len(), Ordering::SeqCst);
+                        let id = queue.next_id;
+                        queue.next_id += 1;
+                        queue.inners.insert(id, None);
+                        (id, item)
+                    }
+                    None => {
+                        queue.running = false;
+                        let done = queue.outer_completed && queue.inners.is_empty();
+                        drop(queue);
+                        if done {
+                            self.observer.clone().on_completed()
+                        }
+                        return;
+                    }
+                }
+            };
+            let next = {
+                let observer = self.observer.clone();
+                move |item| observer.on_next(item)
+            };
+            let complete = {
+                let limited = self.clone();
+                move || limited.inner_completed(id)
+            };
+            let error = {
+                let limited = self.clone();
+                move |error| limited.error(error)
+            };
+            let sub = (self.and_then)(item).subscribe((next, error, complete));
+            let stale = {
+                let mut queue = self.queue.lock().unwrap();
+                let closed = queue.closed;
+                match queue.inners.get_mut(&id) {
+                    Some(slot) if !closed => {
+                        slot.replace(sub);
+                        None
+                    }
+                    _ => Some(sub),
+                }
+            };
+            if let Some(stale) = stale {
+                stale.unsubscribe()
+            }
+        }
+    }
+
+    fn inner_completed(&self, id: u64) {
+        self.queue.lock().unwrap().inners.remove(&id);
+        self.run()
+    }
+
+    fn complete(&self) {
+        self.queue.lock().unwrap().outer_completed = true;
+        self.run()
+    }
+
+    fn close(&self) {
+        self.outer.cancel();
+        let inners: Vec<_> = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.closed = true;
+            queue.items.clear();
+            self.pending.len.store(0, Ordering::SeqCst);
+            queue.inners.drain().filter_map(|(_, sub)| sub).collect()
+        };
+        inners.into_iter().for_each(|sub| sub.unsubscribe());
+    }
+
+    fn error(&self, error: OO::Error) {
+        self.close();
+        self.observer.clone().on_error(error)
+    }
+}
+
+impl<'a, FM, O, OO> Observable<'a> for FlatMapLimitedObservable<FM, O>
+    where O: Observable<'a, Error=OO::Error> + 'a,
+          OO: Observable<'a> + 'a,
+          FM: Fn(O::Item) -> OO + Send + Sync + 'a,
+          O::Item: Send + Sync + 'a,
+{
+    type Item = OO::Item;
+    type Error = OO::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let limited = FlatMapLimited {
+            queue: Arc::new(Mutex::new(Queue {
+                items: VecDeque::new(),
+                running: false,
+                next_id: 0,
+                inners: HashMap::new(),
+                outer_completed: false,
+                closed: false,
+            })),
+            outer: SubscriptionSlot::new(),
+            observer: BaseObserver::new(observer),
+            and_then: Arc::new(self.and_then),
+            max_concurrent: self.max_concurrent,
+            overflow: self.overflow,
+            pending: self.pending,
+        };
+        let next = {
+            let limited = limited.clone();
+            move |item| limited.next(item)
+        };
+        let complete = {
+            let limited = limited.clone();
+            move || limited.complete()
+        };
+        let error = {
+            let limited = limited.clone();
+            move |error| limited.error(error)
+        };
+        limited.outer.set(self.original.subscribe((next, error, complete)));
+        Subscription::new(move || limited.close())
+    }
+}
+
+struct Exhaust<'a> {
+    generation: u64,
+    active: bool,
+    inner: Option<Subscription<'a>>,
+    outer_completed: bool,
+}
+
+impl<'a, FM, O, OO> Observable<'a> for ExhaustMapObservable<FM, O>
+    where O: Observable<'a, Error=OO::Error> + 'a,
+          OO: Observable<'a> + 'a,
+          FM: Fn(O::Item) -> OO + Send + Sync + 'a,
+{
+    type Item = OO::Item;
+    type Error = OO::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let exhaust_map = self.exhaust_map;
+        let observer = BaseObserver::new(observer);
+        let state = Arc::new(Mutex::new(Exhaust { generation: 0, active: false, inner: None, outer_completed: false }));
+        let next = {
+            let observer = observer.clone();
+            let state = state.clone();
+            move |item| {
+                let generation = {
+                    let mut state = state.lock().unwrap();
+                    if state.active {
+                        return;
+                    }
+                    state.active = true;
+                    state.generation += 1;
+                    state.generation
+                };
+                let next = {
+                    let observer = observer.clone();
+                    move |item| observer.on_next(item)
+                };
+                let complete = {
+                    let observer = observer.clone();
+                    let state = state.clone();
+                    move || {
+                        let done = {
+                            let mut state = state.lock().unwrap();
+                            if state.generation != generation {
+                                return;
+                            }
+                            state.active = false;
+                            state.inner = None;
+                            state.outer_completed
+                        };
+                        if done {
+                            observer.on_completed()
+                        }
+                    }
+                };
+                let error = {
+                    let observer = observer.clone();
+                    move |error| observer.on_error(error)
+                };
+                let sub = exhaust_map(item).subscribe((next, error, complete));
+                let mut state = state.lock().unwrap();
+                if state.active && state.generation == generation {
+                    state.inner = Some(sub);
+                } else {
+                    drop(state);
+                    sub.unsubscribe()
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let state = state.clone();
+            move || {
+                let done = {
+                    let mut state = state.lock().unwrap();
+                    state.outer_completed = true;
+                    !state.active
+                };
+                if done {
+                    observer.on_completed()
+                }
+            }
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            let inner = state.lock().unwrap().inner.take();
+            if let Some(inner) = inner {
+                inner.unsubscribe()
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::sync::{Arc, Mutex};
     use crate::prelude::*;
-    use crate::{Subject, BaseObservable};
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+    use crate::{Subject, BaseObservable, OverflowStrategy, TestScheduler};
+    use std::time::Duration;
 
     #[test]
     fn it_works() {
+        let test = TestScheduler::new();
+        let scheduler = test.scheduler();
         let input = Subject::<i64, &'static str>::new();
-        let data = Arc::new(Mutex::new(Vec::new()));
-        {
-            let data = data.clone();
-            let finish = data.clone();
-            input.fork()
-                .and_then(move |x| {
-                    BaseObservable::new(move |sub| {
-                        let millis = std::time::Duration::from_millis(50);
-                        std::thread::sleep(millis);
-                        std::thread::spawn(move || {
-                            sub.on_next(x + 1);
-                            let millis = std::time::Duration::from_millis(50);
-                            std::thread::sleep(millis);
-                            sub.on_next(x + 2);
-                            let millis = std::time::Duration::from_millis(50);
-                            std::thread::sleep(millis);
-                            sub.on_completed();
-                        });
-                    })
+        let observer = TestObserver::new();
+        input.fork()
+            .and_then(move |x| {
+                let scheduler = scheduler.clone();
+                BaseObservable::new(move |sub| {
+                    let first = sub.clone();
+                    scheduler.run_after(Duration::from_millis(10), move || first.on_next(x + 1));
+                    let second = sub.clone();
+                    scheduler.run_after(Duration::from_millis(60), move || second.on_next(x + 2));
+                    scheduler.run_after(Duration::from_millis(110), move || sub.on_completed());
                 })
-                .subscribe((
-                    move |x| {
-                        data.lock().unwrap().push(x);
-                    },
-                    move |_| {
-                        assert_eq!("this", "never happen");
-                    },
-                    move || {
-                        finish.lock().unwrap().push(10);
-                    },
-                ));
-        }
+            })
+            .subscribe(observer.fork());
 
         input.on_next(1);
-        let millis = std::time::Duration::from_millis(50);
-        std::thread::sleep(millis);
+        test.advance_by(Duration::from_millis(50));
         input.on_next(2);
-        let millis = std::time::Duration::from_millis(50);
-        std::thread::sleep(millis);
+        test.advance_by(Duration::from_millis(50));
         input.on_next(3);
-        let millis = std::time::Duration::from_millis(50);
-        std::thread::sleep(millis);
+        test.advance_by(Duration::from_millis(50));
         input.on_completed();
 
-        let millis = std::time::Duration::from_millis(20);
-        std::thread::sleep(millis);
-        assert_eq!(&vec![2, 3, 3, 4, 4, 5], &*data.lock().unwrap());
+        test.advance_by(Duration::from_millis(20));
+        observer.assert_values(&[2, 3, 3, 4, 4, 5]).assert_not_terminated();
+
+        test.run();
+        observer.assert_values(&[2, 3, 3, 4, 4, 5]).assert_completed();
+    }
 
-        let millis = std::time::Duration::from_millis(200);
-        std::thread::sleep(millis);
-        assert_eq!(&vec![2, 3, 3, 4, 4, 5, 10], &*data.lock().unwrap());
+    #[test]
+    fn synchronous_inner() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![factory::from_iter(vec![1, 2]), factory::from_iter(vec![3])])
+            .and_then(|inner| inner)
+            .subscribe(observer.fork());
+        observer.assert_values(&[1, 2, 3]).assert_completed();
+    }
+
+    #[test]
+    fn and_then_limited() {
+        let test = MarbleTest::new();
+        let values = [('a', 0), ('b', 1), ('c', 2), ('x', 10), ('y', 20), ('z', 30)];
+        let inners = [
+            test.cold::<usize, ()>("--x|", &values),
+            test.cold::<usize, ()>("----y|", &values),
+            test.cold::<usize, ()>("-z|", &values),
+        ];
+        let third = inners[2].subscriptions();
+        let source = test.cold::<usize, ()>("-abc---|", &values);
+        test.expect_observable(source.and_then_limited(2, move |x| inners[x].fork()))
+            .to_be("---x-zy|", &values);
+        test.expect_subscriptions(third).to_be(&["----^-!"]);
+
+        let input = Subject::<i32, ()>::new();
+        let limited = input.fork().merge_map(1, |_| factory::never::<i32, ()>());
+        let pending = limited.pending();
+        limited.subscribe(|_| {});
+        input.on_next(1);
+        input.on_next(2);
+        input.on_next(3);
+        assert_eq!(pending.len(), 2);
+    }
+
+    #[test]
+    fn and_then_limited_inner_error() {
+        let test = MarbleTest::new();
+        let values = [('a', 0), ('b', 1), ('c', 2)];
+        let inner = test.cold_with_error::<usize, &str>("--#", &values, "failed");
+        let source = test.cold::<usize, &str>("-a-b-c-|", &values);
+        let source_log = source.subscriptions();
+        test.expect_observable(source.and_then_limited(1, move |_| inner.fork()))
+            .to_be_with_error("---#", &values, "failed");
+        test.expect_subscriptions(source_log).to_be(&["^--!"]);
+
+        let input = Subject::<i32, ()>::new();
+        let failing = Subject::<i32, ()>::new();
+        let inner = failing.fork();
+        let limited = input.fork().merge_map(1, move |_| inner.fork());
+        let pending = limited.pending();
+        limited.subscribe(TestObserver::new());
+        input.on_next(1);
+        input.on_next(2);
+        failing.on_error(());
+        input.on_next(3);
+        assert_eq!(pending.len(), 0);
+    }
+
+    #[test]
+    fn overflow() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('v', 0)];
+        let inner = test.cold::<i32, ()>("--v|", &values);
+        let source = test.cold::<i32, ()>("-abc----|", &values);
+        let forked = inner.fork();
+        test.expect_observable(source.and_then_limited(1, move |x| forked.fork().map(move |_| x))
+            .with_overflow(1, OverflowStrategy::DropOldest))
+            .to_be("---a--c-|", &values);
+
+        let source = test.cold::<i32, ()>("-abc----|", &values);
+        test.expect_observable(source.and_then_limited(1, move |x| inner.fork().map(move |_| x))
+            .with_overflow(1, OverflowStrategy::DropNewest))
+            .to_be("---a--b-|", &values);
+    }
+
+    #[test]
+    fn exhaust_map() {
+        let test = MarbleTest::new();
+        let values = [('a', 0), ('b', 1), ('c', 2), ('x', 10), ('y', 20), ('z', 30)];
+        let inners = [
+            test.cold::<usize, ()>("--x-y|", &values),
+            test.cold::<usize, ()>("-x|", &values),
+            test.cold::<usize, ()>("-z|", &values),
+        ];
+        let dropped = inners[1].subscriptions();
+        let source = test.cold::<usize, ()>("-a-b---c|", &values);
+        test.expect_observable(source.exhaust_map(move |x| inners[x].fork()))
+            .to_be("---x-y--z|", &values);
+        test.expect_subscriptions(dropped).to_be(&[]);
     }
 }
@@ -0,0 +1,195 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+
+pub struct ForkJoinObservable<O> {
+    observables: Vec<O>,
+}
+
+impl<O> ForkJoinObservable<O> {
+    pub(crate) fn new(observables: Vec<O>) -> Self {
+        Self { observables }
+    }
+}
+
+pub struct ForkJoin2Observable<O, OO> {
+    original: O,
+    other: OO,
+}
+
+impl<O, OO> ForkJoin2Observable<O, OO> {
+    pub(crate) fn new(original: O, other: OO) -> Self {
+        Self { original, other }
+    }
+}
+
+struct Last<I> {
+    items: Vec<Option<I>>,
+    remaining: usize,
+}
+
+impl<'a, O> Observable<'a> for ForkJoinObservable<O> where O: Observable<'a> + 'a, O::Item: Send + Sync + 'a {
+    type Item = Vec<O::Item>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let count = self.observables.len();
+        if count == 0 {
+            observer.on_completed();
+            return Subscription::new(|| {});
+        }
+        let last = Arc::new(Mutex::new(Last { items: (0..count).map(|_| None).collect(), remaining: count }));
+        let subs: Vec<_> = self.observables.into_iter().enumerate()
+            .map(|(index, observable)| {
+                let next = {
+                    let last = last.clone();
+                    move |item| {
+                        last.lock().unwrap().items[index] = Some(item);
+                    }
+                };
+                let complete = {
+                    let observer = observer.clone();
+                    let last = last.clone();
+                    move || {
+                        let (done, row) = {
+                            let mut last = last.lock().unwrap();
+                            last.remaining -= 1;
+                            if last.items[index].is_none() {
+                                (true, None)
+                            } else if last.remaining == 0 {
+                                (true, last.items.drain(..).collect::<Option<Vec<_>>>())
+                            } else {
+                                (false, None)
+                            }
+                        };
+                        if let Some(row) = row {
+                            observer.on_next(row)
+                        }
+                        if done {
+                            observer.on_completed()
+                        }
+                    }
+                };
+                let error = {
+                    let observer = observer.clone();
+                    move |error| observer.on_error(error)
+                };
+                observable.subscribe((next, error, complete))
+            })
+            .collect();
+        Subscription::new(move || subs.into_iter().for_each(|sub| sub.unsubscribe()))
+    }
+}
+
+struct LastPair<A, B> {
+    left: Option<A>,
+    right: Option<B>,
+    completed: [bool; 2],
+}
+
+impl<A, B> LastPair<A, B> {
+    /// Marks side `index` complete, returning whether the join is over and the pair to emit, if any.
+    fn complete(&mut self, index: usize) -> (bool, Option<(A, B)>) {
+        self.completed[index] = true;
+        let emitted = if index == 0 { self.left.is_some() } else { self.right.is_some() };
+        if !emitted {
+            (true, None)
+        } else if self.completed.iter().all(|completed| *completed) {
+            (true, self.left.take().zip(self.right.take()))
+        } else {
+            (false, None)
+        }
+    }
+}
+
+impl<'a, O, OO> Observable<'a> for ForkJoin2Observable<O, OO>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Error=O::Error> + 'a,
+          O::Item: Send + Sync + 'a,
+          OO::Item: Send + Sync + 'a,
+{
+    type Item = (O::Item, OO::Item);
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let last = Arc::new(Mutex::new(LastPair { left: None, right: None, completed: [false; 2] }));
+        let next_1 = {
+            let last = last.clone();
+            move |item| {
+                last.lock().unwrap().left = Some(item);
+            }
+        };
+        let next_2 = {
+            let last = last.clone();
+            move |item| {
+                last.lock().unwrap().right = Some(item);
+            }
+        };
+        let complete = |index: usize| {
+            let observer = observer.clone();
+            let last = last.clone();
+            move || {
+                let (done, pair) = last.lock().unwrap().complete(index);
+                if let Some(pair) = pair {
+                    observer.on_next(pair)
+                }
+                if done {
+                    observer.on_completed()
+                }
+            }
+        };
+        let (complete_1, complete_2) = (complete(0), complete(1));
+        let error = move |error| observer.on_error(error);
+        let sub1 = self.original.subscribe((next_1, error.clone(), complete_1));
+        let sub2 = self.other.subscribe((next_2, error, complete_2));
+        Subscription::new(move || {
+            sub1.unsubscribe();
+            sub2.unsubscribe();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::factory;
+    use crate::testing::MarbleTest;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+        let sources = vec![
+            test.cold::<i32, ()>("-a-b|", &values),
+            test.cold::<i32, ()>("--c---d|", &values),
+        ];
+        test.expect_observable(factory::fork_join(sources))
+            .to_be("-------(x|)", &[('x', vec![2, 4])]);
+
+        let sources = vec![
+            test.cold::<i32, ()>("-a-b|", &values),
+            test.cold::<i32, ()>("--|", &values),
+        ];
+        test.expect_observable(factory::fork_join(sources))
+            .to_be("--|", &[]);
+
+        let sources = vec![
+            test.cold_with_error::<i32, &str>("-a-b|", &values, "unused"),
+            test.cold_with_error::<i32, &str>("--#", &values, "failed"),
+        ];
+        test.expect_observable(factory::fork_join(sources))
+            .to_be_with_error("--#", &[], "failed");
+    }
+
+    #[test]
+    fn fork_join2() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3)];
+        let left = test.cold::<i32, ()>("-a-b|", &values);
+        let right = test.cold::<&str, ()>("--x|", &[('x', "x")]);
+        test.expect_observable(factory::fork_join2(left, right))
+            .to_be("----(p|)", &[('p', (2, "x"))]);
+    }
+}
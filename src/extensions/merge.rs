@@ -1,5 +1,6 @@
 use crate::observable::Observable;
 use crate::observer::Observer;
+use crate::extensions::flat_map::{FlatMapExt, FlatMapObservable, Flatten};
 use crate::{Subscription, BaseObserver};
 use std::sync::{Mutex, Arc};
 
@@ -8,10 +9,26 @@ pub struct MergeObservable<O, OO> {
     other: OO,
 }
 
+pub struct MergeVecObservable<O> {
+    observables: Vec<O>,
+}
+
+impl<O> MergeVecObservable<O> {
+    pub(crate) fn new(observables: Vec<O>) -> Self {
+        Self { observables }
+    }
+}
+
 pub trait MergeExt<'a>: Observable<'a> + Sized {
     fn merge<O>(self, other: O) -> MergeObservable<Self, O> where O: Observable<'a, Item=<Self as Observable<'a>>::Item, Error=<Self as Observable<'a>>::Error> + 'a {
         MergeObservable { original: self, other }
     }
+
+    /// Mirrors every observable emitted by the source concurrently. Completes once the source and
+    /// all of them have completed.
+    fn merge_all(self) -> FlatMapObservable<Flatten<Self::Item>, Self> where Self::Item: Observable<'a, Error=Self::Error> + 'a {
+        self.and_then(std::convert::identity)
+    }
 }
 
 impl<'a, O> MergeExt<'a> for O where O: Observable<'a> {}
@@ -26,21 +43,25 @@ impl<'a, O, OO> Observable<'a> for MergeObservable<O, OO> where O: Observable<'a
             let observer = observer.clone();
             move |item| observer.on_next(item)
         };
-        let complete = {
-            let completed = Arc::new(Mutex::new(false));
+        let completed = Arc::new(Mutex::new([false; 2]));
+        let complete = |index: usize| {
             let observer = observer.clone();
+            let completed = completed.clone();
             move || {
-                if *&*completed.lock().unwrap() {
+                let done = {
+                    let mut completed = completed.lock().unwrap();
+                    completed[index] = true;
+                    completed.iter().all(|completed| *completed)
+                };
+                if done {
                     observer.on_completed()
-                } else {
-                    *completed.lock().unwrap() = true;
                 }
             }
         };
+        let (complete_1, complete_2) = (complete(0), complete(1));
         let error = move |error| observer.on_error(error);
-        let obs = (next, error, complete);
-        let sub1 = self.original.subscribe(obs.clone());
-        let sub2 = self.other.subscribe(obs);
+        let sub1 = self.original.subscribe((next.clone(), error.clone(), complete_1));
+        let sub2 = self.other.subscribe((next, error, complete_2));
         Subscription::new(move || {
             sub1.unsubscribe();
             sub2.unsubscribe();
@@ -48,28 +69,60 @@ impl<'a, O, OO> Observable<'a> for MergeObservable<O, OO> where O: Observable<'a
     }
 }
 
+impl<'a, O> Observable<'a> for MergeVecObservable<O> where O: Observable<'a> + 'a {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let remaining = Arc::new(Mutex::new(self.observables.len()));
+        if self.observables.is_empty() {
+            observer.on_completed();
+            return Subscription::new(|| {});
+        }
+        let subs: Vec<_> = self.observables.into_iter()
+            .map(|observable| {
+                let next = {
+                    let observer = observer.clone();
+                    move |item| observer.on_next(item)
+                };
+                let complete = {
+                    let observer = observer.clone();
+                    let remaining = remaining.clone();
+                    move || {
+                        let done = {
+                            let mut remaining = remaining.lock().unwrap();
+                            *remaining -= 1;
+                            *remaining == 0
+                        };
+                        if done {
+                            observer.on_completed()
+                        }
+                    }
+                };
+                let error = {
+                    let observer = observer.clone();
+                    move |error| observer.on_error(error)
+                };
+                observable.subscribe((next, error, complete))
+            })
+            .collect();
+        Subscription::new(move || subs.into_iter().for_each(|sub| sub.unsubscribe()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::sync::{Arc, Mutex};
     use crate::prelude::*;
-    use crate::Subject;
+    use crate::{factory, Subject};
+    use crate::testing::{MarbleTest, TestObserver};
 
     #[test]
     fn it_works() {
         let input = Subject::<i64, ()>::new();
         let input2 = Subject::<i64, ()>::new();
-        let data = Arc::new(Mutex::new(Vec::new()));
-        {
-            let data = data.clone();
-            let finish = data.clone();
-            input.fork()
-                .merge(input2.fork())
-                .subscribe((
-                    move |x| { data.lock().unwrap().push(x); },
-                    move |_| {},
-                    move || { finish.lock().unwrap().push(10); }
-                ));
-        }
+        let observer = TestObserver::new();
+        input.fork().merge(input2.fork()).subscribe(observer.fork());
 
         input.on_next(1);
         input2.on_next(2);
@@ -78,8 +131,38 @@ mod tests {
         input.on_next(2);
         input.on_completed();
         input2.on_next(3);
+        observer.assert_values(&[1, 2, 3, 1, 2, 3]).assert_not_terminated();
+
         input2.on_completed();
+        observer.assert_completed();
+    }
+
+    #[test]
+    fn merge_vec() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+        let sources = vec![
+            test.cold::<i32, ()>("-a---|", &values),
+            test.cold::<i32, ()>("--b|", &values),
+            test.cold::<i32, ()>("---c-d-|", &values),
+        ];
+        test.expect_observable(factory::merge(sources))
+            .to_be("-abc-d-|", &values);
+    }
+
+    #[test]
+    fn merge_all() {
+        let test = MarbleTest::new();
+        let values = [('a', 0), ('b', 1), ('x', 10), ('y', 20), ('z', 30)];
+        let inners = [test.cold::<usize, ()>("-x---y|", &values), test.cold::<usize, ()>("-z|", &values)];
+        let source = test.cold::<usize, ()>("-a-b|", &values);
+        test.expect_observable(source.map(move |x| inners[x].fork()).merge_all())
+            .to_be("--x-z-y|", &values);
 
-        assert_eq!(&vec![1, 2, 3, 1, 2, 3, 10], &*data.lock().unwrap());
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![factory::from_iter(vec![1, 2]), factory::from_iter(vec![3])])
+            .merge_all()
+            .subscribe(observer.fork());
+        observer.assert_values(&[1, 2, 3]).assert_completed();
     }
 }
@@ -9,5 +9,29 @@ pub mod skip;
 pub mod fold;
 pub mod merge;
 pub mod combine;
+pub mod debounce;
+pub mod throttle;
+pub mod delay;
+pub mod timeout;
+pub mod buffer_time;
+pub mod window_time;
+pub mod buffer;
+pub mod window;
+pub mod sample;
+pub mod timestamp;
+pub mod distinct;
+pub mod distinct_until_changed;
 pub mod flat_map;
+pub mod switch_map;
+pub mod concat;
+pub mod zip;
+pub mod with_latest_from;
+pub mod fork_join;
+pub mod race;
+pub mod start_with;
+pub mod default_if_empty;
+pub mod take_while;
+pub mod skip_while;
+pub mod take_until;
+pub mod skip_until;
 pub mod thread;
\ No newline at end of file
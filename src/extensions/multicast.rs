@@ -72,43 +72,38 @@ impl<'a, I, E> Observable<'a> for Multicast<'a, I, E> {
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
-    use crate::BaseObservable;
+    use crate::{BaseObservable, TestScheduler};
     use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     #[test]
     fn it_works() {
-        let obs = BaseObservable::<i32, ()>::new(|sub| {
-            std::thread::spawn(move || {
-                let millis = std::time::Duration::from_millis(10);
-                std::thread::sleep(millis);
-                sub.on_next(1);
-
-                let millis = std::time::Duration::from_millis(10);
-                std::thread::sleep(millis);
-                sub.on_next(2);
-
-                let millis = std::time::Duration::from_millis(10);
-                std::thread::sleep(millis);
-                sub.on_next(3);
-            });
+        let test = TestScheduler::new();
+        let scheduler = test.scheduler();
+        let obs = BaseObservable::<i32, ()>::new(move |sub| {
+            for x in 1..=3 {
+                let sub = sub.clone();
+                scheduler.run_after(Duration::from_millis(10 * x as u64), move || sub.on_next(x));
+            }
         }).share();
         let share_data = Arc::new(Mutex::new(Vec::new()));
         {
             let data = share_data.clone();
             obs.fork().subscribe(move |x: Arc<i32>| {
-                data.lock().unwrap().push(*&*x);
+                data.lock().unwrap().push(*x);
             });
             let data = share_data.clone();
             obs.fork().subscribe(move |x: Arc<i32>| {
-                data.lock().unwrap().push(*&*x);
+                data.lock().unwrap().push(*x);
             });
             let data = share_data.clone();
             obs.fork().subscribe(move |x: Arc<i32>| {
-                data.lock().unwrap().push(*&*x);
+                data.lock().unwrap().push(*x);
             });
         }
-        let millis = std::time::Duration::from_millis(50);
-        std::thread::sleep(millis);
+        test.advance_by(Duration::from_millis(20));
+        assert_eq!(&vec![1, 1, 1, 2, 2, 2], &*share_data.lock().unwrap());
+        test.advance_by(Duration::from_millis(10));
         assert_eq!(&vec![1, 1, 1, 2, 2, 2, 3, 3, 3], &*share_data.lock().unwrap());
     }
 }
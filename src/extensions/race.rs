@@ -0,0 +1,186 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+
+pub struct RaceObservable<O> {
+    observables: Vec<O>,
+}
+
+impl<O> RaceObservable<O> {
+    pub(crate) fn new(observables: Vec<O>) -> Self {
+        Self { observables }
+    }
+}
+
+pub struct RaceWithObservable<O, OO> {
+    original: O,
+    other: OO,
+}
+
+pub trait RaceExt<'a>: Observable<'a> + Sized {
+    /// Mirrors whichever of this observable and `other` notifies first, unsubscribing from the other.
+    fn race_with<OO>(self, other: OO) -> RaceWithObservable<Self, OO>
+        where OO: Observable<'a, Item=Self::Item, Error=Self::Error> + 'a
+    {
+        RaceWithObservable { original: self, other }
+    }
+}
+
+impl<'a, O> RaceExt<'a> for O where O: Observable<'a> {}
+
+struct Race<'a> {
+    winner: Option<usize>,
+    subs: Vec<Option<Subscription<'a>>>,
+}
+
+impl<'a> Race<'a> {
+    fn new(count: usize) -> Self {
+        Self { winner: None, subs: (0..count).map(|_| None).collect() }
+    }
+
+    /// Lets source `index` through if it is, or becomes, the winner, unsubscribing every other source.
+    fn claim(race: &Mutex<Self>, index: usize) -> bool {
+        let losers: Vec<_> = {
+            let mut race = race.lock().unwrap();
+            match race.winner {
+                Some(winner) => return winner == index,
+                None => race.winner = Some(index),
+            }
+            race.subs.iter_mut().enumerate()
+                .filter(|(other, _)| *other != index)
+                .filter_map(|(_, sub)| sub.take())
+                .collect()
+        };
+        losers.into_iter().for_each(|sub| sub.unsubscribe());
+        true
+    }
+
+    fn is_decided(race: &Mutex<Self>) -> bool {
+        race.lock().unwrap().winner.is_some()
+    }
+
+    fn store(race: &Mutex<Self>, index: usize, sub: Subscription<'a>) {
+        let mut race = race.lock().unwrap();
+        if race.winner.is_none_or(|winner| winner == index) {
+            race.subs[index] = Some(sub);
+        } else {
+            drop(race);
+            sub.unsubscribe()
+        }
+    }
+
+    fn cancel(race: &Mutex<Self>) {
+        let subs: Vec<_> = race.lock().unwrap().subs.iter_mut().filter_map(Option::take).collect();
+        subs.into_iter().for_each(|sub| sub.unsubscribe());
+    }
+
+    fn subscribe<O>(race: &Arc<Mutex<Self>>, index: usize, observable: O, observer: &BaseObserver<'a, O::Item, O::Error>)
+        where O: Observable<'a> + 'a
+    {
+        if Race::is_decided(race) {
+            return;
+        }
+        let next = {
+            let race = race.clone();
+            let observer = observer.clone();
+            move |item| {
+                if Race::claim(&race, index) {
+                    observer.on_next(item)
+                }
+            }
+        };
+        let complete = {
+            let race = race.clone();
+            let observer = observer.clone();
+            move || {
+                if Race::claim(&race, index) {
+                    observer.on_completed()
+                }
+            }
+        };
+        let error = {
+            let race = race.clone();
+            let observer = observer.clone();
+            move |error| {
+                if Race::claim(&race, index) {
+                    observer.on_error(error)
+                }
+            }
+        };
+        let sub = observable.subscribe((next, error, complete));
+        Race::store(race, index, sub);
+    }
+}
+
+impl<'a, O> Observable<'a> for RaceObservable<O> where O: Observable<'a> + 'a {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        if self.observables.is_empty() {
+            observer.on_completed();
+            return Subscription::new(|| {});
+        }
+        let race = Arc::new(Mutex::new(Race::new(self.observables.len())));
+        self.observables.into_iter().enumerate()
+            .for_each(|(index, observable)| Race::subscribe(&race, index, observable, &observer));
+        Subscription::new(move || Race::cancel(&race))
+    }
+}
+
+impl<'a, O, OO> Observable<'a> for RaceWithObservable<O, OO>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Item=O::Item, Error=O::Error> + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let race = Arc::new(Mutex::new(Race::new(2)));
+        Race::subscribe(&race, 0, self.original, &observer);
+        Race::subscribe(&race, 1, self.other, &observer);
+        Subscription::new(move || Race::cancel(&race))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3)];
+        let sources = vec![
+            test.cold::<i32, ()>("---a|", &values),
+            test.cold::<i32, ()>("-b-c|", &values),
+            test.cold::<i32, ()>("--|", &values),
+        ];
+        let subscriptions: Vec<_> = sources.iter().map(|source| source.subscriptions()).collect();
+        test.expect_observable(factory::race(sources))
+            .to_be("-b-c|", &values);
+        test.expect_subscriptions(subscriptions[0].clone()).to_be(&["^!"]);
+        test.expect_subscriptions(subscriptions[2].clone()).to_be(&["^!"]);
+    }
+
+    #[test]
+    fn race_with() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2)];
+        let primary = test.cold::<i32, ()>("---a|", &values);
+        let fallback = test.cold::<i32, ()>("--|", &values);
+        let subscriptions = primary.subscriptions();
+        test.expect_observable(primary.race_with(fallback))
+            .to_be("--|", &values);
+        test.expect_subscriptions(subscriptions).to_be(&["^-!"]);
+
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2]).race_with(factory::from_iter(vec![3])).subscribe(observer.fork());
+        observer.assert_values(&[1, 2]).assert_completed();
+    }
+}
@@ -0,0 +1,176 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Scheduler, Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub struct SampleObservable<O, N> {
+    original: O,
+    notifier: N,
+}
+
+pub struct SampleTimeObservable<O> {
+    period: Duration,
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub trait SampleExt<'a>: Observable<'a> + Sized {
+    /// Emits the latest item whenever `notifier` emits, skipping the tick if nothing arrived since
+    /// the previous sample. Completion of `notifier` is ignored.
+    fn sample<N>(self, notifier: N) -> SampleObservable<Self, N> where N: Observable<'a, Error=Self::Error> + 'a {
+        SampleObservable { original: self, notifier }
+    }
+
+    /// Like `sample`, but samples every `period`.
+    fn sample_time(self, period: Duration) -> SampleTimeObservable<Self> {
+        self.sample_time_on(period, Scheduler::new_thread())
+    }
+
+    fn sample_time_on(self, period: Duration, scheduler: Scheduler) -> SampleTimeObservable<Self> {
+        SampleTimeObservable { period, scheduler, original: self }
+    }
+}
+
+impl<'a, O> SampleExt<'a> for O where O: Observable<'a> {}
+
+fn stop(sub: &Mutex<Option<Subscription<'_>>>) {
+    let sub = sub.lock().unwrap().take();
+    if let Some(sub) = sub {
+        sub.unsubscribe()
+    }
+}
+
+impl<'a, O, N> Observable<'a> for SampleObservable<O, N>
+    where O: Observable<'a> + 'a,
+          N: Observable<'a, Error=O::Error> + 'a,
+          O::Item: Send + Sync + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let latest = Arc::new(Mutex::new(None));
+        let notifier = Arc::new(Mutex::new(None));
+        let tick = {
+            let observer = observer.clone();
+            let latest = latest.clone();
+            move |_| {
+                let item = latest.lock().unwrap().take();
+                if let Some(item) = item {
+                    observer.on_next(item)
+                }
+            }
+        };
+        let notifier_error = {
+            let observer = observer.clone();
+            move |error| observer.on_error(error)
+        };
+        let notifier_sub = self.notifier.subscribe((tick, notifier_error, || {}));
+        notifier.lock().unwrap().replace(notifier_sub);
+        let next = {
+            let latest = latest.clone();
+            move |item| {
+                latest.lock().unwrap().replace(item);
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let notifier = notifier.clone();
+            move || {
+                stop(&notifier);
+                observer.on_completed()
+            }
+        };
+        let error = {
+            let notifier = notifier.clone();
+            move |error| {
+                stop(&notifier);
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            stop(&notifier);
+        })
+    }
+}
+
+impl<O> Observable<'static> for SampleTimeObservable<O> where O: Observable<'static> + 'static, O::Item: Send + Sync {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let observer = BaseObserver::new(observer);
+        let latest = Arc::new(Mutex::new(None));
+        let timer = {
+            let observer = observer.clone();
+            let latest = latest.clone();
+            self.scheduler.run_periodic(self.period, self.period, move || {
+                let item = latest.lock().unwrap().take();
+                if let Some(item) = item {
+                    observer.on_next(item)
+                }
+            })
+        };
+        let timer = Arc::new(Mutex::new(Some(timer)));
+        let next = move |item| {
+            latest.lock().unwrap().replace(item);
+        };
+        let complete = {
+            let observer = observer.clone();
+            let timer = timer.clone();
+            move || {
+                stop(&timer);
+                observer.on_completed()
+            }
+        };
+        let error = {
+            let timer = timer.clone();
+            move |error| {
+                stop(&timer);
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            stop(&timer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('x', 0)];
+        let source = test.cold::<i32, ()>("-a-b---c---|", &values);
+        let notifier = test.cold::<i32, ()>("--x---x-x-x-|", &values);
+        let subscriptions = notifier.subscriptions();
+        test.expect_observable(source.sample(notifier))
+            .to_be("--a---b-c--|", &values);
+        test.expect_subscriptions(subscriptions).to_be(&["^----------!"]);
+
+        let source = test.cold::<i32, ()>("-a-b---", &values);
+        let notifier = test.cold::<i32, ()>("--x-#", &values);
+        test.expect_observable(source.sample(notifier))
+            .to_be("--a-#", &values);
+    }
+
+    #[test]
+    fn sample_time() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3)];
+        let source = test.cold::<i32, ()>("-ab----c--|", &values);
+        test.expect_observable(source.sample_time_on(Duration::from_millis(3), test.scheduler()))
+            .to_be("---b-----c|", &values);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::observable::{Observable, SubscriptionSlot};
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub struct SkipUntilObservable<O, N> {
+    original: O,
+    notifier: N,
+}
+
+pub trait SkipUntilExt<'a>: Observable<'a> + Sized {
+    /// Drops items until `notifier` emits, then unsubscribes from it and mirrors the source.
+    fn skip_until<N>(self, notifier: N) -> SkipUntilObservable<Self, N>
+        where N: Observable<'a, Error=Self::Error> + 'a
+    {
+        SkipUntilObservable { original: self, notifier }
+    }
+}
+
+impl<'a, O> SkipUntilExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, O, N> Observable<'a> for SkipUntilObservable<O, N>
+    where O: Observable<'a> + 'a,
+          N: Observable<'a, Error=O::Error> + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let open = Arc::new(AtomicBool::new(false));
+        let source = SubscriptionSlot::new();
+        let notifier = SubscriptionSlot::new();
+        let notifier_next = {
+            let open = open.clone();
+            let notifier = notifier.clone();
+            move |_| {
+                open.store(true, Ordering::SeqCst);
+                notifier.cancel()
+            }
+        };
+        let notifier_error = {
+            let observer = observer.clone();
+            let source = source.clone();
+            move |error| {
+                source.cancel();
+                observer.on_error(error)
+            }
+        };
+        notifier.set(self.notifier.subscribe((notifier_next, notifier_error, || {})));
+        if source.is_cancelled() {
+            return Subscription::new(|| {});
+        }
+        let next = {
+            let observer = observer.clone();
+            move |item| {
+                if open.load(Ordering::SeqCst) {
+                    observer.on_next(item)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let notifier = notifier.clone();
+            move || {
+                notifier.cancel();
+                observer.on_completed()
+            }
+        };
+        let error = {
+            let notifier = notifier.clone();
+            move |error| {
+                notifier.cancel();
+                observer.on_error(error)
+            }
+        };
+        source.set(self.original.subscribe((next, error, complete)));
+        Subscription::new(move || {
+            source.cancel();
+            notifier.cancel();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('x', 0)];
+        let source = test.cold::<i32, ()>("-a-b-c|", &values);
+        let start = test.cold::<i32, ()>("--x---|", &values);
+        let start_log = start.subscriptions();
+        test.expect_observable(source.skip_until(start))
+            .to_be("---b-c|", &values);
+        test.expect_subscriptions(start_log).to_be(&["^-!"]);
+    }
+
+    #[test]
+    fn terminal_events() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('x', 0)];
+        let source = test.cold::<i32, ()>("-a|", &values);
+        let start = test.cold::<i32, ()>("----x|", &values);
+        let start_log = start.subscriptions();
+        test.expect_observable(source.skip_until(start))
+            .to_be("--|", &values);
+        test.expect_subscriptions(start_log).to_be(&["^-!"]);
+
+        let source = test.cold_with_error::<i32, &str>("-a---|", &values, "unused");
+        let start = test.cold_with_error::<i32, &str>("--#", &values, "failed");
+        let source_log = source.subscriptions();
+        test.expect_observable(source.skip_until(start))
+            .to_be_with_error("--#", &values, "failed");
+        test.expect_subscriptions(source_log).to_be(&["^-!"]);
+    }
+
+    #[test]
+    fn notifier_fires_on_subscribe() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2]).skip_until(factory::from_value(())).subscribe(observer.fork());
+        observer.assert_values(&[1, 2]).assert_completed();
+    }
+}
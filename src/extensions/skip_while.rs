@@ -0,0 +1,78 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub struct SkipWhileObservable<F, O> {
+    predicate: F,
+    original: O,
+}
+
+pub trait SkipWhileExt<'a>: Observable<'a> + Sized {
+    /// Drops items while `predicate` holds, then mirrors the source from the first item that fails it.
+    fn skip_while<F>(self, predicate: F) -> SkipWhileObservable<F, Self> where F: Fn(&Self::Item) -> bool + Send + Sync + 'a {
+        SkipWhileObservable { predicate, original: self }
+    }
+}
+
+impl<'a, O> SkipWhileExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, F, O> Observable<'a> for SkipWhileObservable<F, O>
+    where O: Observable<'a> + 'a,
+          F: Fn(&O::Item) -> bool + Send + Sync + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let predicate = self.predicate;
+        let observer = BaseObserver::new(observer);
+        let skipping = AtomicBool::new(true);
+        let next = {
+            let observer = observer.clone();
+            move |item| {
+                if skipping.load(Ordering::SeqCst) {
+                    if predicate(&item) {
+                        return;
+                    }
+                    skipping.store(false, Ordering::SeqCst);
+                }
+                observer.on_next(item)
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || observer.on_completed()
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+
+    #[test]
+    fn it_works() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2, 5, 3]).skip_while(|x| *x < 4).subscribe(observer.fork());
+        observer.assert_values(&[5, 3]).assert_completed();
+    }
+
+    #[test]
+    fn forwards_terminal_events() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 5), ('c', 3)];
+        let source = test.cold_with_error::<i32, &str>("-a-b-c#", &values, "failed");
+        test.expect_observable(source.skip_while(|x| *x < 4))
+            .to_be_with_error("---b-c#", &values, "failed");
+
+        let source = test.cold::<i32, ()>("-a-|", &values);
+        test.expect_observable(source.skip_while(|x| *x < 4))
+            .to_be("---|", &values);
+    }
+}
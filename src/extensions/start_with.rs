@@ -0,0 +1,104 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::iter::Once;
+
+pub struct StartWithObservable<O, It> {
+    items: It,
+    original: O,
+}
+
+pub struct EndWithObservable<O, I> {
+    item: I,
+    original: O,
+}
+
+pub trait StartWithExt<'a>: Observable<'a> + Sized {
+    /// Emits `value` on subscription, before any item of the source.
+    fn start_with(self, value: Self::Item) -> StartWithObservable<Self, Once<Self::Item>> {
+        self.start_with_iter(std::iter::once(value))
+    }
+
+    fn start_with_iter<It>(self, items: It) -> StartWithObservable<Self, It> where It: IntoIterator<Item=Self::Item> {
+        StartWithObservable { items, original: self }
+    }
+
+    /// Emits `value` when the source completes, right before completing.
+    fn end_with(self, value: Self::Item) -> EndWithObservable<Self, Self::Item> {
+        EndWithObservable { item: value, original: self }
+    }
+}
+
+impl<'a, O> StartWithExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, O, It> Observable<'a> for StartWithObservable<O, It> where O: Observable<'a> + 'a, It: IntoIterator<Item=O::Item> {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        self.items.into_iter().for_each(|item| observer.on_next(item));
+        let next = {
+            let observer = observer.clone();
+            move |item| observer.on_next(item)
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || observer.on_completed()
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+impl<'a, O> Observable<'a> for EndWithObservable<O, O::Item> where O: Observable<'a> + 'a, O::Item: Send + Sync + 'a {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let item = self.item;
+        let observer = BaseObserver::new(observer);
+        let next = {
+            let observer = observer.clone();
+            move |item| observer.on_next(item)
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || {
+                observer.on_next(item);
+                observer.on_completed()
+            }
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+
+    #[test]
+    fn it_works() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![3, 4]).start_with(2).start_with_iter(vec![0, 1]).subscribe(observer.fork());
+        observer.assert_values(&[0, 1, 2, 3, 4]).assert_completed();
+    }
+
+    #[test]
+    fn end_with() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('z', 0)];
+        let source = test.cold::<i32, ()>("-a--|", &values);
+        test.expect_observable(source.end_with(0))
+            .to_be("-a--(z|)", &values);
+
+        let source = test.cold::<i32, ()>("-a-#", &values);
+        test.expect_observable(source.end_with(0))
+            .to_be("-a-#", &values);
+    }
+}
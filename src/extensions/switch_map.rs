@@ -0,0 +1,185 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::extensions::flat_map::Flatten;
+use crate::{Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+
+pub struct SwitchMapObservable<F, O> {
+    switch_map: F,
+    original: O,
+}
+
+pub trait SwitchMapExt<'a>: Observable<'a> + Sized {
+    /// Maps each item to an observable and mirrors only the latest one, unsubscribing from the
+    /// previous inner observable as soon as a new item arrives.
+    fn switch_map<F, OO>(self, switch_map: F) -> SwitchMapObservable<F, Self>
+        where OO: Observable<'a, Error=Self::Error> + 'a, F: Fn(Self::Item) -> OO + Send + Sync + 'a
+    {
+        SwitchMapObservable { switch_map, original: self }
+    }
+
+    /// Mirrors the latest observable emitted by the source.
+    fn switch_all(self) -> SwitchMapObservable<Flatten<Self::Item>, Self>
+        where Self::Item: Observable<'a, Error=Self::Error> + 'a
+    {
+        self.switch_map(std::convert::identity)
+    }
+}
+
+impl<'a, O> SwitchMapExt<'a> for O where O: Observable<'a> {}
+
+struct Switch<'a> {
+    generation: u64,
+    inner: Option<Subscription<'a>>,
+    inner_active: bool,
+    outer_completed: bool,
+}
+
+impl<'a> Switch<'a> {
+    fn is_current(state: &Mutex<Self>, generation: u64) -> bool {
+        state.lock().unwrap().generation == generation
+    }
+
+    /// Unsubscribes the current inner observable and starts a new generation.
+    fn cancel(state: &Mutex<Self>) -> u64 {
+        let (generation, inner) = {
+            let mut state = state.lock().unwrap();
+            state.generation += 1;
+            state.inner_active = false;
+            (state.generation, state.inner.take())
+        };
+        if let Some(inner) = inner {
+            inner.unsubscribe()
+        }
+        generation
+    }
+}
+
+impl<'a, F, O, OO> Observable<'a> for SwitchMapObservable<F, O>
+    where O: Observable<'a, Error=OO::Error> + 'a,
+          OO: Observable<'a> + 'a,
+          F: Fn(O::Item) -> OO + Send + Sync + 'a,
+{
+    type Item = OO::Item;
+    type Error = OO::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let switch_map = self.switch_map;
+        let observer = BaseObserver::new(observer);
+        let state = Arc::new(Mutex::new(Switch { generation: 0, inner: None, inner_active: false, outer_completed: false }));
+        let next = {
+            let observer = observer.clone();
+            let state = state.clone();
+            move |item| {
+                let observable = switch_map(item);
+                let generation = Switch::cancel(&state);
+                state.lock().unwrap().inner_active = true;
+                let next = {
+                    let observer = observer.clone();
+                    let state = state.clone();
+                    move |item| {
+                        if Switch::is_current(&state, generation) {
+                            observer.on_next(item)
+                        }
+                    }
+                };
+                let complete = {
+                    let observer = observer.clone();
+                    let state = state.clone();
+                    move || {
+                        let done = {
+                            let mut state = state.lock().unwrap();
+                            if state.generation != generation {
+                                return;
+                            }
+                            state.inner_active = false;
+                            state.inner = None;
+                            state.outer_completed
+                        };
+                        if done {
+                            observer.on_completed()
+                        }
+                    }
+                };
+                let error = {
+                    let observer = observer.clone();
+                    let state = state.clone();
+                    move |error| {
+                        if Switch::is_current(&state, generation) {
+                            observer.on_error(error)
+                        }
+                    }
+                };
+                let sub = observable.subscribe((next, error, complete));
+                let mut state = state.lock().unwrap();
+                if state.generation == generation && state.inner_active {
+                    state.inner = Some(sub);
+                } else {
+                    drop(state);
+                    sub.unsubscribe()
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let state = state.clone();
+            move || {
+                let done = {
+                    let mut state = state.lock().unwrap();
+                    state.outer_completed = true;
+                    !state.inner_active
+                };
+                if done {
+                    observer.on_completed()
+                }
+            }
+        };
+        let error = {
+            let state = state.clone();
+            move |error| {
+                Switch::cancel(&state);
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            Switch::cancel(&state);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 0), ('b', 1), ('x', 10), ('y', 20), ('z', 30)];
+        let inners = [test.cold::<usize, ()>("-x-y-z|", &values), test.cold::<usize, ()>("-x-y|", &values)];
+        let first = inners[0].subscriptions();
+        let second = inners[1].subscriptions();
+        let source = test.cold::<usize, ()>("-a---b----|", &values);
+        test.expect_observable(source.switch_map(move |x| inners[x].fork()))
+            .to_be("--x-y-x-y-|", &values);
+        test.expect_subscriptions(first).to_be(&["-^---!"]);
+        test.expect_subscriptions(second).to_be(&["-----^---!"]);
+
+        let inner = test.cold::<usize, ()>("--x|", &values);
+        let source = test.cold::<usize, ()>("-a|", &values);
+        test.expect_observable(source.switch_map(move |_| inner.fork()))
+            .to_be("---x|", &values);
+    }
+
+    #[test]
+    fn switch_all() {
+        let test = MarbleTest::new();
+        let values = [('a', 0), ('b', 1), ('x', 10), ('y', 20)];
+        let inners = [test.cold::<usize, ()>("-x-y-", &values), test.cold::<usize, ()>("y|", &values)];
+        let source = test.cold::<usize, ()>("-a--b-|", &values);
+        test.expect_observable(source.map(move |x| inners[x].fork()).switch_all())
+            .to_be("--x-y-|", &values);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::observable::{Observable, SubscriptionSlot};
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+
+pub struct TakeUntilObservable<O, N> {
+    original: O,
+    notifier: N,
+}
+
+pub trait TakeUntilExt<'a>: Observable<'a> + Sized {
+    /// Mirrors the source until `notifier` emits, then completes and unsubscribes from both.
+    fn take_until<N>(self, notifier: N) -> TakeUntilObservable<Self, N>
+        where N: Observable<'a, Error=Self::Error> + 'a
+    {
+        TakeUntilObservable { original: self, notifier }
+    }
+}
+
+impl<'a, O> TakeUntilExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, O, N> Observable<'a> for TakeUntilObservable<O, N>
+    where O: Observable<'a> + 'a,
+          N: Observable<'a, Error=O::Error> + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let source = SubscriptionSlot::new();
+        let notifier = SubscriptionSlot::new();
+        let notifier_next = {
+            let observer = observer.clone();
+            let source = source.clone();
+            let notifier = notifier.clone();
+            move |_| {
+                source.cancel();
+                notifier.cancel();
+                observer.clone().on_completed()
+            }
+        };
+        let notifier_error = {
+            let observer = observer.clone();
+            let source = source.clone();
+            move |error| {
+                source.cancel();
+                observer.on_error(error)
+            }
+        };
+        notifier.set(self.notifier.subscribe((notifier_next, notifier_error, || {})));
+        if source.is_cancelled() {
+            return Subscription::new(|| {});
+        }
+        let next = {
+            let observer = observer.clone();
+            move |item| observer.on_next(item)
+        };
+        let complete = {
+            let observer = observer.clone();
+            let notifier = notifier.clone();
+            move || {
+                notifier.cancel();
+                observer.on_completed()
+            }
+        };
+        let error = {
+            let notifier = notifier.clone();
+            move |error| {
+                notifier.cancel();
+                observer.on_error(error)
+            }
+        };
+        source.set(self.original.subscribe((next, error, complete)));
+        Subscription::new(move || {
+            source.cancel();
+            notifier.cancel();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('x', 0)];
+        let source = test.cold::<i32, ()>("-a-b-c|", &values);
+        let shutdown = test.cold::<i32, ()>("----x|", &values);
+        let (source_log, shutdown_log) = (source.subscriptions(), shutdown.subscriptions());
+        test.expect_observable(source.take_until(shutdown))
+            .to_be("-a-b|", &values);
+        test.expect_subscriptions(source_log).to_be(&["^---!"]);
+        test.expect_subscriptions(shutdown_log).to_be(&["^---!"]);
+    }
+
+    #[test]
+    fn source_terminates_first() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('x', 0)];
+        let source = test.cold::<i32, ()>("-a|", &values);
+        let shutdown = test.cold::<i32, ()>("----x|", &values);
+        let shutdown_log = shutdown.subscriptions();
+        test.expect_observable(source.take_until(shutdown))
+            .to_be("-a|", &values);
+        test.expect_subscriptions(shutdown_log).to_be(&["^-!"]);
+
+        let source = test.cold::<i32, ()>("-a---|", &values);
+        let shutdown = test.cold::<i32, ()>("--|", &values);
+        test.expect_observable(source.take_until(shutdown))
+            .to_be("-a---|", &values);
+    }
+
+    #[test]
+    fn notifier_fires_on_subscribe() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2]).take_until(factory::from_value(())).subscribe(observer.fork());
+        observer.assert_values(&[]).assert_completed();
+    }
+}
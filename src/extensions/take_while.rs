@@ -0,0 +1,92 @@
+use crate::observable::{Observable, SubscriptionSlot};
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+
+pub struct TakeWhileObservable<F, O> {
+    predicate: F,
+    inclusive: bool,
+    original: O,
+}
+
+pub trait TakeWhileExt<'a>: Observable<'a> + Sized {
+    /// Mirrors the source while `predicate` holds, completing at the first item that fails it.
+    fn take_while<F>(self, predicate: F) -> TakeWhileObservable<F, Self> where F: Fn(&Self::Item) -> bool + Send + Sync + 'a {
+        TakeWhileObservable { predicate, inclusive: false, original: self }
+    }
+
+    /// Like `take_while`, but also emits the item that failed `predicate` before completing.
+    fn take_while_inclusive<F>(self, predicate: F) -> TakeWhileObservable<F, Self> where F: Fn(&Self::Item) -> bool + Send + Sync + 'a {
+        TakeWhileObservable { predicate, inclusive: true, original: self }
+    }
+}
+
+impl<'a, O> TakeWhileExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, F, O> Observable<'a> for TakeWhileObservable<F, O>
+    where O: Observable<'a> + 'a,
+          F: Fn(&O::Item) -> bool + Send + Sync + 'a,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let predicate = self.predicate;
+        let inclusive = self.inclusive;
+        let observer = BaseObserver::new(observer);
+        let upstream = SubscriptionSlot::new();
+        let next = {
+            let observer = observer.clone();
+            let upstream = upstream.clone();
+            move |item| {
+                if upstream.is_cancelled() {
+                    return;
+                }
+                if predicate(&item) {
+                    observer.on_next(item)
+                } else {
+                    upstream.cancel();
+                    if inclusive {
+                        observer.on_next(item)
+                    }
+                    observer.clone().on_completed()
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || observer.on_completed()
+        };
+        let error = move |error| observer.on_error(error);
+        upstream.set(self.original.subscribe((next, error, complete)));
+        Subscription::new(move || upstream.cancel())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 5), ('d', 3)];
+        let source = test.cold::<i32, ()>("-a-b-c-d|", &values);
+        let subscriptions = source.subscriptions();
+        test.expect_observable(source.take_while(|x| *x < 4))
+            .to_be("-a-b-|", &values);
+        test.expect_subscriptions(subscriptions).to_be(&["^----!"]);
+    }
+
+    #[test]
+    fn inclusive() {
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2, 5, 3]).take_while_inclusive(|x| *x < 4).subscribe(observer.fork());
+        observer.assert_values(&[1, 2, 5]).assert_completed();
+
+        let observer = TestObserver::<i32, ()>::new();
+        factory::from_iter(vec![1, 2]).take_while_inclusive(|x| *x < 4).subscribe(observer.fork());
+        observer.assert_values(&[1, 2]).assert_completed();
+    }
+}
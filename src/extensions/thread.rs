@@ -1,6 +1,7 @@
 use crate::observable::Observable;
 use crate::observer::Observer;
-use crate::{Scheduler, Subscription, BaseObserver};
+use crate::{Scheduler, Subscription, BaseObserver, Notification};
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 pub struct ThreadObservable<O> {
@@ -8,10 +9,19 @@ pub struct ThreadObservable<O> {
     original: O,
 }
 
+pub struct ObserveOnObservable<O> {
+    scheduler: Scheduler,
+    original: O,
+}
+
 pub trait ThreadExt<'a>: Observable<'a> + Sized {
     fn subscribe_on(self, scheduler: Scheduler) -> ThreadObservable<Self> where Self: 'a {
         ThreadObservable { scheduler, original: self }
     }
+
+    fn observe_on(self, scheduler: Scheduler) -> ObserveOnObservable<Self> where Self: 'a {
+        ObserveOnObservable { scheduler, original: self }
+    }
 }
 
 impl<'a, O> ThreadExt<'a> for O where O: Observable<'a> {}
@@ -38,36 +48,165 @@ impl<O> Observable<'static> for ThreadObservable<O> where O: Observable<'static>
     }
 }
 
+/// Notifications waiting to be delivered in order, by whichever caller is draining.
+pub(crate) struct Mailbox<I, E> {
+    pending: VecDeque<Notification<I, E>>,
+    draining: bool,
+}
+
+impl<I, E> Mailbox<I, E> {
+    pub(crate) fn new() -> Self {
+        Self { pending: VecDeque::new(), draining: false }
+    }
+
+    /// Queues `notification`, returning whether the caller must now `drain` the mailbox.
+    pub(crate) fn post(&mut self, notification: Notification<I, E>) -> bool {
+        self.pending.push_back(notification);
+        !std::mem::replace(&mut self.draining, true)
+    }
+}
+
+impl<O> Observable<'static> for ObserveOnObservable<O>
+    where O: Observable<'static> + 'static,
+          O::Item: Send + Sync,
+          O::Error: Send + Sync,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let scheduler = self.scheduler;
+        let observer = BaseObserver::new(observer);
+        let mailbox = Arc::new(Mutex::new(Mailbox::new()));
+        let deliver = {
+            let observer = observer.clone();
+            move |notification| {
+                if mailbox.lock().unwrap().post(notification) {
+                    let mailbox = mailbox.clone();
+                    let observer = observer.clone();
+                    scheduler.run(move || drain(&mailbox, |notification| match notification {
+                        Notification::Next(item) => observer.on_next(item),
+                        Notification::Error(error) => observer.clone().on_error(error),
+                        Notification::Completed => observer.clone().on_completed(),
+                    }));
+                }
+            }
+        };
+        let next = {
+            let deliver = deliver.clone();
+            move |item| deliver(Notification::Next(item))
+        };
+        let error = {
+            let deliver = deliver.clone();
+            move |error| deliver(Notification::Error(error))
+        };
+        let complete = move || deliver(Notification::Completed);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            observer.dispose();
+        })
+    }
+}
+
+/// Hands queued notifications to `deliver` until the mailbox is empty. Notifications posted meanwhile,
+/// from other threads or from inside `deliver`, are handed over by this loop in posting order.
+pub(crate) fn drain<I, E>(mailbox: &Mutex<Mailbox<I, E>>, deliver: impl Fn(Notification<I, E>)) {
+    loop {
+        let notification = {
+            let mut state = mailbox.lock().unwrap();
+            match state.pending.pop_front() {
+                Some(notification) => notification,
+                None => {
+                    state.draining = false;
+                    return;
+                }
+            }
+        };
+        deliver(notification)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
     use crate::prelude::*;
     use crate::BaseObservable;
-    use crate::Scheduler;
+    use crate::{Subject, TestScheduler};
+    use crate::testing::TestObserver;
+    use std::time::Duration;
 
     #[test]
     fn it_works() {
+        let test = TestScheduler::new();
+        let scheduler = test.scheduler();
+        let obs = BaseObservable::<i32, ()>::new(move |sub| {
+            scheduler.run_after(Duration::from_millis(20), move || {
+                sub.on_next(1);
+                sub.on_next(2);
+                sub.on_next(3);
+                sub.on_completed();
+            });
+        });
+        let observer = TestObserver::new();
+        obs.subscribe_on(test.scheduler()).subscribe(observer.fork());
+        observer.assert_values(&[]);
+        test.advance_by(Duration::from_millis(19));
+        observer.assert_values(&[]).assert_not_terminated();
+        test.advance_by(Duration::from_millis(1));
+        observer.assert_values(&[1, 2, 3]).assert_completed();
+    }
+
+    #[test]
+    fn virtual_time() {
+        let test = TestScheduler::new();
         let obs = BaseObservable::<i32, ()>::new(|sub| {
-            let ten_millis = std::time::Duration::from_millis(20);
-            std::thread::sleep(ten_millis);
             sub.on_next(1);
             sub.on_next(2);
-            sub.on_next(3);
             sub.on_completed();
         });
         let data = Arc::new(Mutex::new(Vec::new()));
         {
             let data = data.clone();
+            let finish = data.clone();
             obs
-                .subscribe_on(Scheduler::new_thread())
-                .subscribe(move |x| {
-                    data.lock().unwrap().push(x);
-                });
+                .subscribe_on(test.scheduler())
+                .subscribe((
+                    move |x| data.lock().unwrap().push(x),
+                    |_| {},
+                    move || finish.lock().unwrap().push(10),
+                ));
+        }
+        assert!(data.lock().unwrap().is_empty());
+        test.advance_by(Duration::from_millis(0));
+        assert_eq!(&vec![1, 2, 10], &*data.lock().unwrap());
+    }
+
+    #[test]
+    fn observe_on() {
+        let test = TestScheduler::new();
+        let input = Subject::<i32, ()>::new();
+        let data = Arc::new(Mutex::new(Vec::new()));
+        {
+            let data = data.clone();
+            let finish = data.clone();
+            input.fork()
+                .observe_on(test.scheduler())
+                .subscribe((
+                    move |x| data.lock().unwrap().push(x),
+                    |_| {},
+                    move || finish.lock().unwrap().push(10),
+                ));
         }
-        let expected: Vec<i32> = vec![];
-        assert_eq!(&expected, &*data.lock().unwrap());
-        let ten_millis = std::time::Duration::from_millis(50);
-        std::thread::sleep(ten_millis);
-        assert_eq!(&vec![1, 2, 3], &*data.lock().unwrap());
+        input.on_next(1);
+        input.on_next(2);
+        assert!(data.lock().unwrap().is_empty());
+        test.run();
+        assert_eq!(&vec![1, 2], &*data.lock().unwrap());
+
+        input.on_next(3);
+        input.on_completed();
+        test.run();
+        assert_eq!(&vec![1, 2, 3, 10], &*data.lock().unwrap());
     }
 }
@@ -0,0 +1,229 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Scheduler, Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Which items of a throttle window get through: the one that opens it (`leading`), the last one
+/// seen before it closes (`trailing`), or both.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrottleConfig {
+    pub leading: bool,
+    pub trailing: bool,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self { leading: true, trailing: false }
+    }
+}
+
+pub struct ThrottleObservable<O> {
+    duration: Duration,
+    config: ThrottleConfig,
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub trait ThrottleExt<'a>: Observable<'a> + Sized {
+    /// Lets at most one item through per `duration` window.
+    fn throttle(self, duration: Duration, config: ThrottleConfig) -> ThrottleObservable<Self> {
+        self.throttle_on(duration, config, Scheduler::new_thread())
+    }
+
+    fn throttle_on(self, duration: Duration, config: ThrottleConfig, scheduler: Scheduler) -> ThrottleObservable<Self> {
+        ThrottleObservable { duration, config, scheduler, original: self }
+    }
+
+    /// Trailing-only throttle: the first item opens a window and the latest item is emitted when it closes.
+    fn audit(self, duration: Duration) -> ThrottleObservable<Self> {
+        self.audit_on(duration, Scheduler::new_thread())
+    }
+
+    fn audit_on(self, duration: Duration, scheduler: Scheduler) -> ThrottleObservable<Self> {
+        self.throttle_on(duration, ThrottleConfig { leading: false, trailing: true }, scheduler)
+    }
+}
+
+impl<'a, O> ThrottleExt<'a> for O where O: Observable<'a> {}
+
+struct Window<I> {
+    open: bool,
+    trailing: Option<I>,
+    generation: u64,
+    timer: Option<Subscription<'static>>,
+}
+
+struct Throttle<I: 'static, E: 'static> {
+    window: Arc<Mutex<Window<I>>>,
+    observer: BaseObserver<'static, I, E>,
+    scheduler: Scheduler,
+    duration: Duration,
+    config: ThrottleConfig,
+}
+
+impl<I, E> Clone for Throttle<I, E> {
+    fn clone(&self) -> Self {
+        Self {
+            window: self.window.clone(),
+            observer: self.observer.clone(),
+            scheduler: self.scheduler.clone(),
+            duration: self.duration,
+            config: self.config,
+        }
+    }
+}
+
+impl<I, E> Throttle<I, E> where I: Send + Sync + 'static {
+    fn next(&self, item: I) {
+        let (leading, generation) = {
+            let mut window = self.window.lock().unwrap();
+            if window.open {
+                if self.config.trailing {
+                    window.trailing = Some(item);
+                }
+                return;
+            }
+            window.open = true;
+            window.generation += 1;
+            if self.config.leading {
+                (Some(item), window.generation)
+            } else {
+                if self.config.trailing {
+                    window.trailing = Some(item);
+                }
+                (None, window.generation)
+            }
+        };
+        self.schedule(generation);
+        if let Some(item) = leading {
+            self.observer.on_next(item)
+        }
+    }
+
+    fn schedule(&self, generation: u64) {
+        let throttle = self.clone();
+        let timer = self.scheduler.run_after(self.duration, move || throttle.close(generation));
+        let stale = {
+            let mut window = self.window.lock().unwrap();
+            if window.open && window.generation == generation {
+                window.timer.replace(timer)
+            } else {
+                Some(timer)
+            }
+        };
+        if let Some(timer) = stale {
+            timer.unsubscribe()
+        }
+    }
+
+    /// Ends the window; a trailing item restarts it when leading items are enabled, so a leading
+    /// emission can't follow the trailing one within `duration`.
+    fn close(&self, generation: u64) {
+        let (trailing, restart) = {
+            let mut window = self.window.lock().unwrap();
+            if window.generation != generation {
+                return;
+            }
+            window.timer = None;
+            let trailing = window.trailing.take();
+            if trailing.is_some() && self.config.leading {
+                window.generation += 1;
+                (trailing, Some(window.generation))
+            } else {
+                window.open = false;
+                (trailing, None)
+            }
+        };
+        if let Some(generation) = restart {
+            self.schedule(generation)
+        }
+        if let Some(item) = trailing {
+            self.observer.on_next(item)
+        }
+    }
+
+    fn clear(&self) -> Option<I> {
+        let (trailing, timer) = {
+            let mut window = self.window.lock().unwrap();
+            window.open = false;
+            window.generation += 1;
+            (window.trailing.take(), window.timer.take())
+        };
+        if let Some(timer) = timer {
+            timer.unsubscribe()
+        }
+        trailing
+    }
+
+    fn complete(self) {
+        if let Some(item) = self.clear() {
+            self.observer.on_next(item)
+        }
+        self.observer.on_completed()
+    }
+
+    fn error(self, error: E) {
+        self.clear();
+        self.observer.on_error(error)
+    }
+}
+
+impl<O> Observable<'static> for ThrottleObservable<O> where O: Observable<'static> + 'static, O::Item: Send + Sync {
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let throttle = Throttle {
+            window: Arc::new(Mutex::new(Window { open: false, trailing: None, generation: 0, timer: None })),
+            observer: BaseObserver::new(observer),
+            scheduler: self.scheduler,
+            duration: self.duration,
+            config: self.config,
+        };
+        let next = {
+            let throttle = throttle.clone();
+            move |item| throttle.next(item)
+        };
+        let complete = {
+            let throttle = throttle.clone();
+            move || throttle.complete()
+        };
+        let error = {
+            let throttle = throttle.clone();
+            move |error| throttle.error(error)
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            throttle.clear();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+    use crate::ThrottleConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('d', 4), ('e', 5)];
+        let window = Duration::from_millis(3);
+
+        let source = test.cold::<i32, ()>("-abc----d-e|", &values);
+        test.expect_observable(source.throttle_on(window, ThrottleConfig::default(), test.scheduler()))
+            .to_be("-a------d--|", &values);
+
+        let source = test.cold::<i32, ()>("-abc----d-e|", &values);
+        test.expect_observable(source.throttle_on(window, ThrottleConfig { leading: true, trailing: true }, test.scheduler()))
+            .to_be("-a--c---d--(e|)", &values);
+
+        let source = test.cold::<i32, ()>("-abc----d-e|", &values);
+        test.expect_observable(source.audit_on(window, test.scheduler()))
+            .to_be("----c------(e|)", &values);
+    }
+}
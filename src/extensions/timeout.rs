@@ -0,0 +1,324 @@
+use crate::observable::{Observable, SubscriptionSlot};
+use crate::observer::Observer;
+use crate::{Scheduler, Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeoutError<E> {
+    Timeout,
+    Source(E),
+}
+
+pub struct TimeoutObservable<O> {
+    duration: Duration,
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub struct TimeoutWithObservable<O, OO> {
+    duration: Duration,
+    scheduler: Scheduler,
+    original: O,
+    fallback: OO,
+}
+
+pub trait TimeoutExt<'a>: Observable<'a> + Sized {
+    /// Fails with `TimeoutError::Timeout` when the first item, or the gap between two items, takes longer than `duration`.
+    fn timeout(self, duration: Duration) -> TimeoutObservable<Self> {
+        self.timeout_on(duration, Scheduler::new_thread())
+    }
+
+    fn timeout_on(self, duration: Duration, scheduler: Scheduler) -> TimeoutObservable<Self> {
+        TimeoutObservable { duration, scheduler, original: self }
+    }
+
+    /// Like `timeout`, but unsubscribes from the source and switches to `fallback` instead of failing.
+    fn timeout_with<OO>(self, duration: Duration, fallback: OO) -> TimeoutWithObservable<Self, OO>
+        where OO: Observable<'a, Item=Self::Item, Error=Self::Error> + 'a
+    {
+        self.timeout_with_on(duration, fallback, Scheduler::new_thread())
+    }
+
+    fn timeout_with_on<OO>(self, duration: Duration, fallback: OO, scheduler: Scheduler) -> TimeoutWithObservable<Self, OO>
+        where OO: Observable<'a, Item=Self::Item, Error=Self::Error> + 'a
+    {
+        TimeoutWithObservable { duration, scheduler, original: self, fallback }
+    }
+}
+
+impl<'a, O> TimeoutExt<'a> for O where O: Observable<'a> {}
+
+struct Countdown {
+    generation: u64,
+    timer: Option<Subscription<'static>>,
+    expired: bool,
+}
+
+/// Runs `on_expire` once if `reset` isn't called again within `duration`.
+#[derive(Clone)]
+struct Deadline {
+    countdown: Arc<Mutex<Countdown>>,
+    scheduler: Scheduler,
+    duration: Duration,
+    on_expire: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl Deadline {
+    fn new(scheduler: Scheduler, duration: Duration, on_expire: impl Fn() + Send + Sync + 'static) -> Self {
+        let countdown = Arc::new(Mutex::new(Countdown { generation: 0, timer: None, expired: false }));
+        Self { countdown, scheduler, duration, on_expire: Arc::new(on_expire) }
+    }
+
+    /// Restarts the countdown, or returns `false` if it already expired.
+    fn reset(&self) -> bool {
+        let (generation, previous) = {
+            let mut countdown = self.countdown.lock().unwrap();
+            if countdown.expired {
+                return false;
+            }
+            countdown.generation += 1;
+            (countdown.generation, countdown.timer.take())
+        };
+        if let Some(previous) = previous {
+            previous.unsubscribe()
+        }
+        let timer = {
+            let countdown = self.countdown.clone();
+            let on_expire = self.on_expire.clone();
+            self.scheduler.run_after(self.duration, move || {
+                {
+                    let mut countdown = countdown.lock().unwrap();
+                    if countdown.expired || countdown.generation != generation {
+                        return;
+                    }
+                    countdown.expired = true;
+                    countdown.timer = None;
+                }
+                on_expire()
+            })
+        };
+        let stale = {
+            let mut countdown = self.countdown.lock().unwrap();
+            if !countdown.expired && countdown.generation == generation {
+                countdown.timer.replace(timer)
+            } else {
+                Some(timer)
+            }
+        };
+        if let Some(stale) = stale {
+            stale.unsubscribe()
+        }
+        true
+    }
+
+    /// Stops the countdown for good, returning `false` if it had already expired.
+    fn cancel(&self) -> bool {
+        let (live, timer) = {
+            let mut countdown = self.countdown.lock().unwrap();
+            let live = !countdown.expired;
+            countdown.expired = true;
+            (live, countdown.timer.take())
+        };
+        if let Some(timer) = timer {
+            timer.unsubscribe()
+        }
+        live
+    }
+
+    fn is_expired(&self) -> bool {
+        self.countdown.lock().unwrap().expired
+    }
+}
+
+/// Keeps the source subscription around so an expiring deadline can drop it.
+fn store(source: &Mutex<Option<Subscription<'static>>>, deadline: &Deadline, sub: Subscription<'static>) {
+    let mut source = source.lock().unwrap();
+    if deadline.is_expired() {
+        drop(source);
+        sub.unsubscribe()
+    } else {
+        source.replace(sub);
+    }
+}
+
+fn take(source: &Mutex<Option<Subscription<'static>>>) {
+    let sub = source.lock().unwrap().take();
+    if let Some(sub) = sub {
+        sub.unsubscribe()
+    }
+}
+
+impl<O> Observable<'static> for TimeoutObservable<O> where O: Observable<'static> + 'static {
+    type Item = O::Item;
+    type Error = TimeoutError<O::Error>;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let observer = BaseObserver::new(observer);
+        let source = Arc::new(Mutex::new(None));
+        let deadline = {
+            let observer = observer.clone();
+            let source = source.clone();
+            Deadline::new(self.scheduler, self.duration, move || {
+                take(&source);
+                observer.clone().on_error(TimeoutError::Timeout)
+            })
+        };
+        let next = {
+            let observer = observer.clone();
+            let deadline = deadline.clone();
+            move |item| {
+                if deadline.reset() {
+                    observer.on_next(item)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let deadline = deadline.clone();
+            move || {
+                if deadline.cancel() {
+                    observer.on_completed()
+                }
+            }
+        };
+        let error = {
+            let deadline = deadline.clone();
+            move |error| {
+                if deadline.cancel() {
+                    observer.on_error(TimeoutError::Source(error))
+                }
+            }
+        };
+        deadline.reset();
+        store(&source, &deadline, self.original.subscribe((next, error, complete)));
+        Subscription::new(move || {
+            deadline.cancel();
+            take(&source);
+        })
+    }
+}
+
+impl<O, OO> Observable<'static> for TimeoutWithObservable<O, OO>
+    where O: Observable<'static> + 'static,
+          OO: Observable<'static, Item=O::Item, Error=O::Error> + Send + 'static,
+{
+    type Item = O::Item;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let observer = BaseObserver::new(observer);
+        let source = Arc::new(Mutex::new(None));
+        let fallback = SubscriptionSlot::new();
+        let deadline = {
+            let observer = observer.clone();
+            let source = source.clone();
+            let fallback_sub = fallback.clone();
+            let fallback = Mutex::new(Some(self.fallback));
+            Deadline::new(self.scheduler, self.duration, move || {
+                take(&source);
+                let fallback = fallback.lock().unwrap().take();
+                if let Some(fallback) = fallback {
+                    fallback_sub.set(fallback.subscribe(observer.clone()));
+                }
+            })
+        };
+        let next = {
+            let observer = observer.clone();
+            let deadline = deadline.clone();
+            move |item| {
+                if deadline.reset() {
+                    observer.on_next(item)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let deadline = deadline.clone();
+            move || {
+                if deadline.cancel() {
+                    observer.on_completed()
+                }
+            }
+        };
+        let error = {
+            let deadline = deadline.clone();
+            move |error| {
+                if deadline.cancel() {
+                    observer.on_error(error)
+                }
+            }
+        };
+        deadline.reset();
+        store(&source, &deadline, self.original.subscribe((next, error, complete)));
+        Subscription::new(move || {
+            deadline.cancel();
+            take(&source);
+            fallback.cancel();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::{MarbleTest, TestObserver};
+    use crate::{Subscription, TimeoutError};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3)];
+
+        let source = test.cold::<i32, ()>("-a-b----c|", &values);
+        let subscriptions = source.subscriptions();
+        test.expect_observable(source.timeout_on(Duration::from_millis(3), test.scheduler()))
+            .to_be_with_error("-a-b--#", &values, TimeoutError::Timeout);
+        test.expect_subscriptions(subscriptions).to_be(&["^-----!"]);
+
+        let source = test.cold::<i32, ()>("-a-b#", &values);
+        test.expect_observable(source.timeout_on(Duration::from_millis(3), test.scheduler()))
+            .to_be_with_error("-a-b#", &values, TimeoutError::Source(()));
+    }
+
+    #[test]
+    fn timeout_with() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('x', 10), ('y', 20)];
+        let source = test.cold::<i32, ()>("-a-b----c|", &values);
+        let fallback = test.cold::<i32, ()>("xy|", &values);
+        let subscriptions = source.subscriptions();
+        test.expect_observable(source.timeout_with_on(Duration::from_millis(3), fallback, test.scheduler()))
+            .to_be("-a-b--xy|", &values);
+        test.expect_subscriptions(subscriptions).to_be(&["^-----!"]);
+    }
+
+    #[test]
+    fn unsubscribe_while_switching() {
+        let test = MarbleTest::new();
+        let outer = Arc::new(Mutex::new(None::<Subscription<'static>>));
+        let late = Arc::new(Mutex::new(None));
+        let fallback = {
+            let outer = outer.clone();
+            let late = late.clone();
+            factory::create(move |sub| {
+                if let Some(outer) = outer.lock().unwrap().take() {
+                    outer.unsubscribe()
+                }
+                late.lock().unwrap().replace(sub);
+            })
+        };
+        let observer = TestObserver::<i32, ()>::new();
+        let source = test.cold::<i32, ()>("-----a|", &[('a', 1)]);
+        let sub = source.timeout_with_on(Duration::from_millis(3), fallback, test.scheduler()).subscribe(observer.fork());
+        outer.lock().unwrap().replace(sub);
+        test.flush();
+
+        late.lock().unwrap().take().unwrap().on_next(7);
+        observer.assert_values(&[]).assert_not_terminated();
+    }
+}
+
@@ -0,0 +1,133 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Scheduler, Subscription, BaseObserver};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// An item together with the scheduler time at which it arrived.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timestamped<I> {
+    pub value: I,
+    pub time: Duration,
+}
+
+/// An item together with the time elapsed since the previous item, or since subscription for the first one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeInterval<I> {
+    pub value: I,
+    pub interval: Duration,
+}
+
+pub struct TimestampObservable<O> {
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub struct TimeIntervalObservable<O> {
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub trait TimestampExt<'a>: Observable<'a> + Sized {
+    fn timestamp(self) -> TimestampObservable<Self> {
+        self.timestamp_on(Scheduler::new_thread())
+    }
+
+    fn timestamp_on(self, scheduler: Scheduler) -> TimestampObservable<Self> {
+        TimestampObservable { scheduler, original: self }
+    }
+
+    fn time_interval(self) -> TimeIntervalObservable<Self> {
+        self.time_interval_on(Scheduler::new_thread())
+    }
+
+    fn time_interval_on(self, scheduler: Scheduler) -> TimeIntervalObservable<Self> {
+        TimeIntervalObservable { scheduler, original: self }
+    }
+}
+
+impl<'a, O> TimestampExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, O> Observable<'a> for TimestampObservable<O> where O: Observable<'a> + 'a {
+    type Item = Timestamped<O::Item>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let scheduler = self.scheduler;
+        let observer = BaseObserver::new(observer);
+        let next = {
+            let observer = observer.clone();
+            move |value| observer.on_next(Timestamped { value, time: scheduler.now() })
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || observer.on_completed()
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+impl<'a, O> Observable<'a> for TimeIntervalObservable<O> where O: Observable<'a> + 'a {
+    type Item = TimeInterval<O::Item>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let scheduler = self.scheduler;
+        let observer = BaseObserver::new(observer);
+        let last = Mutex::new(scheduler.now());
+        let next = {
+            let observer = observer.clone();
+            move |value| {
+                let interval = {
+                    let now = scheduler.now();
+                    let mut last = last.lock().unwrap();
+                    let interval = now.saturating_sub(*last);
+                    *last = now;
+                    interval
+                };
+                observer.on_next(TimeInterval { value, interval })
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            move || observer.on_completed()
+        };
+        let error = move |error| observer.on_error(error);
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(|| sub.unsubscribe())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+    use crate::{TimeInterval, Timestamped};
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let source = test.cold::<i32, ()>("-a--b|", &[('a', 1), ('b', 2)]);
+        let stamped = [
+            ('a', Timestamped { value: 1, time: Duration::from_millis(1) }),
+            ('b', Timestamped { value: 2, time: Duration::from_millis(4) }),
+        ];
+        test.expect_observable(source.timestamp_on(test.scheduler()))
+            .to_be("-a--b|", &stamped);
+    }
+
+    #[test]
+    fn time_interval() {
+        let test = MarbleTest::new();
+        let source = test.cold::<i32, ()>("-a--b|", &[('a', 1), ('b', 2)]);
+        let intervals = [
+            ('a', TimeInterval { value: 1, interval: Duration::from_millis(1) }),
+            ('b', TimeInterval { value: 2, interval: Duration::from_millis(3) }),
+        ];
+        test.expect_observable(source.time_interval_on(test.scheduler()))
+            .to_be("-a--b|", &intervals);
+    }
+}
@@ -0,0 +1,132 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subject, Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+
+pub struct WindowObservable<O> {
+    count: usize,
+    original: O,
+}
+
+pub trait WindowExt<'a>: Observable<'a> + Sized {
+    /// Splits the source into consecutive windows of `count` items, each emitted as a `Subject`
+    /// when it opens. A new window opens as soon as the previous one is full.
+    fn window(self, count: usize) -> WindowObservable<Self> {
+        assert!(count > 0, "window count must be positive");
+        WindowObservable { count, original: self }
+    }
+}
+
+impl<'a, O> WindowExt<'a> for O where O: Observable<'a> {}
+
+struct Current<'a, I, E> {
+    seen: usize,
+    window: Option<Subject<'a, I, E>>,
+}
+
+impl<'a, O> Observable<'a> for WindowObservable<O>
+    where O: Observable<'a> + 'a,
+          O::Item: Send + Sync + 'a,
+          O::Error: Clone + Send + Sync + 'a,
+{
+    type Item = Subject<'a, O::Item, O::Error>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let count = self.count;
+        let observer = BaseObserver::new(observer);
+        let window = Subject::new();
+        let current = Arc::new(Mutex::new(Current { seen: 0, window: Some(window.fork()) }));
+        observer.on_next(window);
+        let next = {
+            let observer = observer.clone();
+            let current = current.clone();
+            move |item| {
+                let (window, opened) = {
+                    let mut current = current.lock().unwrap();
+                    current.seen += 1;
+                    if current.seen < count {
+                        (current.window.as_ref().map(Subject::fork), None)
+                    } else {
+                        current.seen = 0;
+                        let opened = Subject::new();
+                        (current.window.replace(opened.fork()), Some(opened))
+                    }
+                };
+                let window = match window {
+                    Some(window) => window,
+                    None => return,
+                };
+                window.on_next(item);
+                if let Some(opened) = opened {
+                    window.on_completed();
+                    observer.on_next(opened)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let current = current.clone();
+            move || {
+                let window = current.lock().unwrap().window.take();
+                if let Some(window) = window {
+                    window.on_completed()
+                }
+                observer.on_completed()
+            }
+        };
+        let error = {
+            let current = current.clone();
+            move |error: O::Error| {
+                let window = current.lock().unwrap().window.take();
+                if let Some(window) = window {
+                    window.on_error(error.clone())
+                }
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            current.lock().unwrap().window.take();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use crate::prelude::*;
+    use crate::Subject;
+
+    #[test]
+    fn it_works() {
+        let input = Subject::<i32, ()>::new();
+        let windows = Arc::new(Mutex::new(Vec::new()));
+        {
+            let windows = windows.clone();
+            input.fork()
+                .window(2)
+                .subscribe(move |window: Subject<i32, ()>| {
+                    let index = {
+                        let mut windows = windows.lock().unwrap();
+                        windows.push(Vec::new());
+                        windows.len() - 1
+                    };
+                    let data = windows.clone();
+                    let finish = windows.clone();
+                    window.subscribe((
+                        move |x| data.lock().unwrap()[index].push(x),
+                        |_| {},
+                        move || finish.lock().unwrap()[index].push(10),
+                    ));
+                });
+        }
+        input.on_next(1);
+        input.on_next(2);
+        input.on_next(3);
+        input.on_completed();
+
+        assert_eq!(&vec![vec![1, 2, 10], vec![3, 10]], &*windows.lock().unwrap());
+    }
+}
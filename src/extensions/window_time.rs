@@ -0,0 +1,130 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Scheduler, Subject, Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub struct WindowTimeObservable<O> {
+    span: Duration,
+    scheduler: Scheduler,
+    original: O,
+}
+
+pub trait WindowTimeExt<'a>: Observable<'a> + Sized {
+    /// Splits the source into consecutive windows of `span`, each emitted as a `Subject` as it opens.
+    fn window_time(self, span: Duration) -> WindowTimeObservable<Self> {
+        self.window_time_on(span, Scheduler::new_thread())
+    }
+
+    fn window_time_on(self, span: Duration, scheduler: Scheduler) -> WindowTimeObservable<Self> {
+        WindowTimeObservable { span, scheduler, original: self }
+    }
+}
+
+impl<'a, O> WindowTimeExt<'a> for O where O: Observable<'a> {}
+
+impl<O> Observable<'static> for WindowTimeObservable<O>
+    where O: Observable<'static> + 'static,
+          O::Item: Send + Sync,
+          O::Error: Clone + Send + Sync,
+{
+    type Item = Subject<'static, O::Item, O::Error>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let observer = BaseObserver::new(observer);
+        let window = Subject::new();
+        let current = Arc::new(Mutex::new(Some(window.fork())));
+        observer.on_next(window);
+        let timer = {
+            let observer = observer.clone();
+            let current = current.clone();
+            self.scheduler.run_periodic(self.span, self.span, move || {
+                let window = Subject::new();
+                let closed = {
+                    let mut current = current.lock().unwrap();
+                    match current.as_ref() {
+                        Some(_) => current.replace(window.fork()),
+                        None => return,
+                    }
+                };
+                if let Some(closed) = closed {
+                    closed.on_completed()
+                }
+                observer.on_next(window)
+            })
+        };
+        let timer = Arc::new(Mutex::new(Some(timer)));
+        let stop = {
+            let timer = timer.clone();
+            move || {
+                let timer = timer.lock().unwrap().take();
+                if let Some(timer) = timer {
+                    timer.unsubscribe()
+                }
+            }
+        };
+        let next = {
+            let current = current.clone();
+            move |item| {
+                if let Some(window) = current.lock().unwrap().as_ref() {
+                    window.on_next(item)
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let current = current.clone();
+            let stop = stop.clone();
+            move || {
+                stop();
+                let window = current.lock().unwrap().take();
+                if let Some(window) = window {
+                    window.on_completed()
+                }
+                observer.on_completed()
+            }
+        };
+        let error = {
+            let current = current.clone();
+            let stop = stop.clone();
+            move |error: O::Error| {
+                stop();
+                let window = current.lock().unwrap().take();
+                if let Some(window) = window {
+                    window.on_error(error.clone())
+                }
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            stop();
+            current.lock().unwrap().take();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('d', 4), ('z', 0)];
+        let source = test.cold::<i32, ()>("-ab-c---d--|", &values);
+        // every window ends with a `z`, so the flattened stream shows where each one closed
+        let windows = source.window_time_on(Duration::from_millis(3), test.scheduler())
+            .and_then(|window| window.end_with(0));
+        test.expect_observable(windows)
+            .to_be("-abzc-z-dz-(z|)", &values);
+
+        let source = test.cold::<i32, ()>("-a--#", &values);
+        test.expect_observable(source.window_time_on(Duration::from_millis(3), test.scheduler()).merge_all())
+            .to_be("-a--#", &values);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::sync::{Arc, Mutex};
+
+pub struct WithLatestFromObservable<O, OO> {
+    original: O,
+    other: OO,
+}
+
+pub trait WithLatestFromExt<'a>: Observable<'a> + Sized {
+    /// Pairs each item with the latest item of `other`, dropping items until `other` has emitted.
+    /// Only this observable triggers emissions, and completion of `other` is ignored.
+    fn with_latest_from<OO>(self, other: OO) -> WithLatestFromObservable<Self, OO>
+        where OO: Observable<'a, Error=Self::Error> + 'a, OO::Item: Clone
+    {
+        WithLatestFromObservable { original: self, other }
+    }
+}
+
+impl<'a, O> WithLatestFromExt<'a> for O where O: Observable<'a> {}
+
+impl<'a, O, OO> Observable<'a> for WithLatestFromObservable<O, OO>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Error=O::Error> + 'a,
+          OO::Item: Clone + Send + Sync + 'a,
+{
+    type Item = (O::Item, OO::Item);
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let latest: Arc<Mutex<Option<OO::Item>>> = Arc::new(Mutex::new(None));
+        let other = {
+            let latest = latest.clone();
+            let observer = observer.clone();
+            let next = move |item| {
+                latest.lock().unwrap().replace(item);
+            };
+            let error = move |error| observer.on_error(error);
+            self.other.subscribe((next, error, || {}))
+        };
+        let other = Arc::new(Mutex::new(Some(other)));
+        let stop = {
+            let other = other.clone();
+            move || {
+                let other = other.lock().unwrap().take();
+                if let Some(other) = other {
+                    other.unsubscribe()
+                }
+            }
+        };
+        let next = {
+            let observer = observer.clone();
+            move |item| {
+                let latest = latest.lock().unwrap().clone();
+                if let Some(latest) = latest {
+                    observer.on_next((item, latest))
+                }
+            }
+        };
+        let complete = {
+            let observer = observer.clone();
+            let stop = stop.clone();
+            move || {
+                stop();
+                observer.on_completed()
+            }
+        };
+        let error = {
+            let stop = stop.clone();
+            move |error| {
+                stop();
+                observer.on_error(error)
+            }
+        };
+        let sub = self.original.subscribe((next, error, complete));
+        Subscription::new(move || {
+            sub.unsubscribe();
+            stop();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('x', 10), ('y', 20)];
+        let source = test.cold::<i32, ()>("-a--b-c|", &values);
+        let other = test.cold::<i32, ()>("--x--y|", &values);
+        let subscriptions = other.subscriptions();
+        test.expect_observable(source.with_latest_from(other))
+            .to_be("----p-q|", &[('p', (2, 10)), ('q', (3, 20))]);
+        test.expect_subscriptions(subscriptions).to_be(&["^-----!"]);
+
+        let source = test.cold::<i32, ()>("-a--b|", &values);
+        let other = test.cold::<i32, ()>("--x-----", &values);
+        let subscriptions = other.subscriptions();
+        test.expect_observable(source.with_latest_from(other))
+            .to_be("----p|", &[('p', (2, 10))]);
+        test.expect_subscriptions(subscriptions).to_be(&["^----!"]);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{Subscription, BaseObserver};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+pub struct ZipObservable<O, OO> {
+    original: O,
+    other: OO,
+}
+
+pub struct ZipVecObservable<O> {
+    observables: Vec<O>,
+}
+
+impl<O> ZipVecObservable<O> {
+    pub(crate) fn new(observables: Vec<O>) -> Self {
+        Self { observables }
+    }
+}
+
+pub trait ZipExt<'a>: Observable<'a> + Sized {
+    /// Pairs the nth item of this observable with the nth item of `other`. Completes once either
+    /// side has completed and has no unmatched items left.
+    fn zip<OO>(self, other: OO) -> ZipObservable<Self, OO> where OO: Observable<'a, Error=Self::Error> + 'a {
+        ZipObservable { original: self, other }
+    }
+}
+
+impl<'a, O> ZipExt<'a> for O where O: Observable<'a> {}
+
+struct Pairs<A, B> {
+    left: VecDeque<A>,
+    right: VecDeque<B>,
+    left_completed: bool,
+    right_completed: bool,
+}
+
+impl<'a, O, OO> Observable<'a> for ZipObservable<O, OO>
+    where O: Observable<'a> + 'a,
+          OO: Observable<'a, Error=O::Error> + 'a,
+          O::Item: Send + Sync + 'a,
+          OO::Item: Send + Sync + 'a,
+{
+    type Item = (O::Item, OO::Item);
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let pairs = Arc::new(Mutex::new(Pairs {
+            left: VecDeque::new(),
+            right: VecDeque::new(),
+            left_completed: false,
+            right_completed: false,
+        }));
+        let next_1 = {
+            let observer = observer.clone();
+            let pairs = pairs.clone();
+            move |item| {
+                let (pair, done) = {
+                    let mut pairs = pairs.lock().unwrap();
+                    match pairs.right.pop_front() {
+                        Some(other) => (Some((item, other)), pairs.right_completed && pairs.right.is_empty()),
+                        None => {
+                            pairs.left.push_back(item);
+                            (None, false)
+                        }
+                    }
+                };
+                if let Some(pair) = pair {
+                    observer.on_next(pair)
+                }
+                if done {
+                    observer.clone().on_completed()
+                }
+            }
+        };
+        let next_2 = {
+            let observer = observer.clone();
+            let pairs = pairs.clone();
+            move |item| {
+                let (pair, done) = {
+                    let mut pairs = pairs.lock().unwrap();
+                    match pairs.left.pop_front() {
+                        Some(other) => (Some((other, item)), pairs.left_completed && pairs.left.is_empty()),
+                        None => {
+                            pairs.right.push_back(item);
+                            (None, false)
+                        }
+                    }
+                };
+                if let Some(pair) = pair {
+                    observer.on_next(pair)
+                }
+                if done {
+                    observer.clone().on_completed()
+                }
+            }
+        };
+        let complete_1 = {
+            let observer = observer.clone();
+            let pairs = pairs.clone();
+            move || {
+                let done = {
+                    let mut pairs = pairs.lock().unwrap();
+                    pairs.left_completed = true;
+                    pairs.left.is_empty()
+                };
+                if done {
+                    observer.on_completed()
+                }
+            }
+        };
+        let complete_2 = {
+            let observer = observer.clone();
+            move || {
+                let done = {
+                    let mut pairs = pairs.lock().unwrap();
+                    pairs.right_completed = true;
+                    pairs.right.is_empty()
+                };
+                if done {
+                    observer.on_completed()
+                }
+            }
+        };
+        let error = move |error| observer.on_error(error);
+        let sub1 = self.original.subscribe((next_1, error.clone(), complete_1));
+        let sub2 = self.other.subscribe((next_2, error, complete_2));
+        Subscription::new(move || {
+            sub1.unsubscribe();
+            sub2.unsubscribe();
+        })
+    }
+}
+
+struct Rows<I> {
+    queues: Vec<VecDeque<I>>,
+    completed: Vec<bool>,
+}
+
+impl<I> Rows<I> {
+    /// Whether some completed source has nothing left to pair.
+    fn exhausted(&self) -> bool {
+        self.queues.iter().zip(&self.completed).any(|(queue, completed)| *completed && queue.is_empty())
+    }
+}
+
+impl<'a, O> Observable<'a> for ZipVecObservable<O> where O: Observable<'a> + 'a, O::Item: Send + Sync + 'a {
+    type Item = Vec<O::Item>;
+    type Error = O::Error;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'a) -> Subscription<'a> {
+        let observer = BaseObserver::new(observer);
+        let count = self.observables.len();
+        if count == 0 {
+            observer.on_completed();
+            return Subscription::new(|| {});
+        }
+        let rows = Arc::new(Mutex::new(Rows {
+            queues: (0..count).map(|_| VecDeque::new()).collect(),
+            completed: vec![false; count],
+        }));
+        let subs: Vec<_> = self.observables.into_iter().enumerate()
+            .map(|(index, observable)| {
+                let next = {
+                    let observer = observer.clone();
+                    let rows = rows.clone();
+                    move |item| {
+                        let (row, done) = {
+                            let mut rows = rows.lock().unwrap();
+                            rows.queues[index].push_back(item);
+                            if rows.queues.iter().all(|queue| !queue.is_empty()) {
+                                let row: Vec<_> = rows.queues.iter_mut().filter_map(VecDeque::pop_front).collect();
+                                (Some(row), rows.exhausted())
+                            } else {
+                                (None, false)
+                            }
+                        };
+                        if let Some(row) = row {
+                            observer.on_next(row)
+                        }
+                        if done {
+                            observer.clone().on_completed()
+                        }
+                    }
+                };
+                let complete = {
+                    let observer = observer.clone();
+                    let rows = rows.clone();
+                    move || {
+                        let done = {
+                            let mut rows = rows.lock().unwrap();
+                            rows.completed[index] = true;
+                            rows.exhausted()
+                        };
+                        if done {
+                            observer.on_completed()
+                        }
+                    }
+                };
+                let error = {
+                    let observer = observer.clone();
+                    move |error| observer.on_error(error)
+                };
+                observable.subscribe((next, error, complete))
+            })
+            .collect();
+        Subscription::new(move || subs.into_iter().for_each(|sub| sub.unsubscribe()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::factory;
+    use crate::testing::MarbleTest;
+
+    #[test]
+    fn it_works() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3), ('x', 10), ('y', 20)];
+        let left = test.cold::<i32, ()>("-a-b--c|", &values);
+        let right = test.cold::<i32, ()>("--x-y|", &values);
+        test.expect_observable(left.zip(right))
+            .to_be("--p-q|", &[('p', (1, 10)), ('q', (2, 20))]);
+
+        let left = test.cold::<i32, ()>("-ab|", &values);
+        let right = test.cold::<i32, ()>("----x--y|", &values);
+        test.expect_observable(left.zip(right))
+            .to_be("----p--(q|)", &[('p', (1, 10)), ('q', (2, 20))]);
+    }
+
+    #[test]
+    fn zip_vec() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2), ('c', 3)];
+        let sources = vec![
+            test.cold::<i32, ()>("-a-b-c|", &values),
+            test.cold::<i32, ()>("--ab|", &values),
+            test.cold::<i32, ()>("---a-b-|", &values),
+        ];
+        test.expect_observable(factory::zip(sources))
+            .to_be("---x-(y|)", &[('x', vec![1, 1, 1]), ('y', vec![2, 2, 2])]);
+    }
+}
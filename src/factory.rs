@@ -1,6 +1,15 @@
 use crate::observer::Observer;
 use crate::observable::Observable;
-use crate::{BaseObserver, BaseObservable, Subject};
+use crate::extensions::concat::ConcatExt;
+use crate::extensions::combine::CombineVecObservable;
+use crate::extensions::fork_join::{ForkJoinObservable, ForkJoin2Observable};
+use crate::extensions::merge::MergeVecObservable;
+use crate::extensions::race::RaceObservable;
+use crate::extensions::zip::ZipVecObservable;
+use crate::{BaseObserver, BaseObservable, Subject, Scheduler, Subscription};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub fn create<'a, I, E>(subscribe: impl FnOnce(BaseObserver<'a, I, E>) + Send + Sync + 'a) -> BaseObservable<'a, I, E> where I: 'a, E: 'a {
     BaseObservable::new(subscribe)
@@ -59,3 +68,148 @@ pub fn from_option<'a, I, E>(opt: Option<I>) -> impl Observable<'a, Item=I, Erro
         }
     })
 }
+
+/// Mirrors each of `observables` in turn, subscribing to the next one when the previous completes.
+pub fn concat<'a, O>(observables: Vec<O>) -> impl Observable<'a, Item=O::Item, Error=O::Error>
+    where O: Observable<'a> + Send + Sync + 'a, O::Error: Send + Sync + 'a
+{
+    from_iter(observables).concat_map(std::convert::identity)
+}
+
+/// Emits a `Vec` of the latest item of every observable whenever one of them emits, once all of
+/// them have emitted. Completes once all of them have completed.
+pub fn combine_latest<'a, O>(observables: Vec<O>) -> CombineVecObservable<O> where O: Observable<'a> {
+    CombineVecObservable::new(observables)
+}
+
+/// Mirrors all of `observables` concurrently, completing once all of them have completed.
+pub fn merge<'a, O>(observables: Vec<O>) -> MergeVecObservable<O> where O: Observable<'a> {
+    MergeVecObservable::new(observables)
+}
+
+/// Emits a `Vec` holding the nth item of every observable, in order. Completes once any of them
+/// has completed with no unmatched items left.
+pub fn zip<'a, O>(observables: Vec<O>) -> ZipVecObservable<O> where O: Observable<'a> {
+    ZipVecObservable::new(observables)
+}
+
+/// Waits for all of `observables` to complete, then emits a `Vec` of their last items. Completes
+/// without emitting if any of them completes without having emitted.
+pub fn fork_join<'a, O>(observables: Vec<O>) -> ForkJoinObservable<O> where O: Observable<'a> {
+    ForkJoinObservable::new(observables)
+}
+
+/// Like `fork_join`, for two observables of different item types.
+pub fn fork_join2<'a, O, OO>(first: O, second: OO) -> ForkJoin2Observable<O, OO>
+    where O: Observable<'a>, OO: Observable<'a, Error=O::Error>
+{
+    ForkJoin2Observable::new(first, second)
+}
+
+/// Mirrors whichever of `observables` notifies first, unsubscribing from all the others.
+pub fn race<'a, O>(observables: Vec<O>) -> RaceObservable<O> where O: Observable<'a> {
+    RaceObservable::new(observables)
+}
+
+/// Emits `0, 1, 2, ...` every `period`, starting one `period` after subscription.
+pub fn interval<E>(period: Duration) -> impl Observable<'static, Item=u64, Error=E> where E: 'static {
+    interval_on(period, Scheduler::new_thread())
+}
+
+pub fn interval_on<E>(period: Duration, scheduler: Scheduler) -> impl Observable<'static, Item=u64, Error=E> where E: 'static {
+    timer_periodic_on(period, period, scheduler)
+}
+
+/// Emits `0` after `delay`, then completes.
+pub fn timer<E>(delay: Duration) -> impl Observable<'static, Item=u64, Error=E> where E: 'static {
+    timer_on(delay, Scheduler::new_thread())
+}
+
+pub fn timer_on<E>(delay: Duration, scheduler: Scheduler) -> impl Observable<'static, Item=u64, Error=E> where E: 'static {
+    TimerObservable { delay, period: None, scheduler, error: PhantomData }
+}
+
+/// Emits `0` after `delay`, then an increasing count every `period`.
+pub fn timer_periodic<E>(delay: Duration, period: Duration) -> impl Observable<'static, Item=u64, Error=E> where E: 'static {
+    timer_periodic_on(delay, period, Scheduler::new_thread())
+}
+
+pub fn timer_periodic_on<E>(delay: Duration, period: Duration, scheduler: Scheduler) -> impl Observable<'static, Item=u64, Error=E> where E: 'static {
+    TimerObservable { delay, period: Some(period), scheduler, error: PhantomData }
+}
+
+struct TimerObservable<E> {
+    delay: Duration,
+    period: Option<Duration>,
+    scheduler: Scheduler,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<E> Observable<'static> for TimerObservable<E> where E: 'static {
+    type Item = u64;
+    type Error = E;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let observer = BaseObserver::new(observer);
+        let timer = {
+            let observer = observer.clone();
+            match self.period {
+                Some(period) => {
+                    let count = Arc::new(Mutex::new(0));
+                    self.scheduler.run_periodic(self.delay, period, move || {
+                        let mut count = count.lock().unwrap();
+                        observer.on_next(*count);
+                        *count += 1;
+                    })
+                }
+                None => self.scheduler.run_after(self.delay, move || {
+                    observer.on_next(0);
+                    observer.on_completed();
+                }),
+            }
+        };
+        Subscription::new(move || {
+            timer.unsubscribe();
+            observer.dispose();
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::TestObserver;
+    use crate::TestScheduler;
+    use std::time::Duration;
+
+    #[test]
+    fn interval() {
+        let test = TestScheduler::new();
+        let observer = TestObserver::<u64, ()>::new();
+        let sub = super::interval_on(Duration::from_millis(10), test.scheduler()).subscribe(observer.fork());
+
+        test.advance_by(Duration::from_millis(35));
+        observer.assert_values(&[0, 1, 2]).assert_not_terminated();
+
+        sub.unsubscribe();
+        test.advance_by(Duration::from_millis(100));
+        observer.assert_values(&[0, 1, 2]);
+    }
+
+    #[test]
+    fn timer() {
+        let test = TestScheduler::new();
+        let once = TestObserver::<u64, ()>::new();
+        let periodic = TestObserver::<u64, ()>::new();
+        super::timer_on(Duration::from_millis(20), test.scheduler()).subscribe(once.fork());
+        super::timer_periodic_on(Duration::from_millis(20), Duration::from_millis(5), test.scheduler()).subscribe(periodic.fork());
+
+        test.advance_by(Duration::from_millis(19));
+        once.assert_values(&[]).assert_not_terminated();
+        periodic.assert_values(&[]);
+
+        test.advance_by(Duration::from_millis(11));
+        once.assert_values(&[0]).assert_completed();
+        periodic.assert_values(&[0, 1, 2]);
+    }
+}
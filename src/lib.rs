@@ -5,6 +5,7 @@ mod scheduler;
 mod extensions;
 
 pub mod factory;
+pub mod testing;
 pub mod prelude {
     pub use crate::observable::{Observable, BoxedObservable};
     pub use crate::observer::Observer;
@@ -20,13 +21,41 @@ pub mod prelude {
     pub use crate::extensions::merge::MergeExt;
     pub use crate::extensions::combine::CombineExt;
     pub use crate::extensions::flat_map::FlatMapExt;
+    pub use crate::extensions::switch_map::SwitchMapExt;
+    pub use crate::extensions::concat::ConcatExt;
+    pub use crate::extensions::zip::ZipExt;
+    pub use crate::extensions::with_latest_from::WithLatestFromExt;
+    pub use crate::extensions::race::RaceExt;
+    pub use crate::extensions::start_with::StartWithExt;
+    pub use crate::extensions::default_if_empty::DefaultIfEmptyExt;
+    pub use crate::extensions::take_while::TakeWhileExt;
+    pub use crate::extensions::skip_while::SkipWhileExt;
+    pub use crate::extensions::take_until::TakeUntilExt;
+    pub use crate::extensions::skip_until::SkipUntilExt;
     pub use crate::extensions::thread::ThreadExt;
+    pub use crate::extensions::debounce::DebounceExt;
+    pub use crate::extensions::throttle::ThrottleExt;
+    pub use crate::extensions::delay::DelayExt;
+    pub use crate::extensions::timeout::TimeoutExt;
+    pub use crate::extensions::buffer_time::BufferTimeExt;
+    pub use crate::extensions::window_time::WindowTimeExt;
+    pub use crate::extensions::buffer::BufferExt;
+    pub use crate::extensions::window::WindowExt;
+    pub use crate::extensions::sample::SampleExt;
+    pub use crate::extensions::timestamp::TimestampExt;
+    pub use crate::extensions::distinct::DistinctExt;
+    pub use crate::extensions::distinct_until_changed::DistinctUntilChangedExt;
 }
 pub use observable::{BaseObservable, Subscription};
-pub use observer::BaseObserver;
+pub use observer::{BaseObserver, Notification};
 pub use subject::Subject;
 pub use extensions::multicast::Multicast;
-pub use scheduler::Scheduler;
+pub use extensions::throttle::ThrottleConfig;
+pub use extensions::timeout::TimeoutError;
+pub use extensions::timestamp::{Timestamped, TimeInterval};
+pub use extensions::distinct::DistinctBound;
+pub use extensions::flat_map::{OverflowStrategy, PendingItems};
+pub use scheduler::{Scheduler, TestScheduler};
 
 #[cfg(test)]
 mod tests {
@@ -1,5 +1,6 @@
 use crate::observer::Observer;
 use crate::BaseObserver;
+use std::sync::{Arc, Mutex};
 
 pub trait Observable<'a> {
     type Item: 'a;
@@ -21,6 +22,50 @@ impl<'a> Subscription<'a> {
     }
 }
 
+struct Slot<'a> {
+    cancelled: bool,
+    subscription: Option<Subscription<'a>>,
+}
+
+/// Holds a subscription that may have to be cancelled before `subscribe` has even returned it,
+/// e.g. when an operator terminates from inside the first `on_next` of a synchronous source.
+#[derive(Clone)]
+pub(crate) struct SubscriptionSlot<'a> {
+    slot: Arc<Mutex<Slot<'a>>>,
+}
+
+impl<'a> SubscriptionSlot<'a> {
+    pub(crate) fn new() -> Self {
+        Self { slot: Arc::new(Mutex::new(Slot { cancelled: false, subscription: None })) }
+    }
+
+    /// Keeps `subscription`, or unsubscribes it right away if the slot was already cancelled.
+    pub(crate) fn set(&self, subscription: Subscription<'a>) {
+        let mut slot = self.slot.lock().unwrap();
+        if slot.cancelled {
+            drop(slot);
+            subscription.unsubscribe()
+        } else {
+            slot.subscription = Some(subscription);
+        }
+    }
+
+    pub(crate) fn cancel(&self) {
+        let subscription = {
+            let mut slot = self.slot.lock().unwrap();
+            slot.cancelled = true;
+            slot.subscription.take()
+        };
+        if let Some(subscription) = subscription {
+            subscription.unsubscribe()
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.slot.lock().unwrap().cancelled
+    }
+}
+
 pub struct BaseObservable<'a, I: 'a, E: 'a> {
     subscribe: Box<dyn FnOnce(BaseObserver<'a, I, E>) + Send + Sync + 'a>,
 }
@@ -57,8 +102,10 @@ impl<'a, O> BoxedObservable<'a> for O where O: Observable<'a> {
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
-    use crate::BaseObservable;
+    use crate::{BaseObservable, TestScheduler};
+    use crate::testing::TestObserver;
     use std::sync::{Mutex, Arc};
+    use std::time::Duration;
 
     #[test]
     fn sync() {
@@ -79,49 +126,25 @@ mod tests {
 
     #[test]
     fn r#async() {
-        let obs = BaseObservable::<i32, ()>::new(|sub| {
-            std::thread::spawn(move || {
-                let millis = std::time::Duration::from_millis(100);
-                std::thread::sleep(millis);
-                sub.on_next(1);
-                sub.on_next(2);
-                sub.on_next(3);
-                let millis = std::time::Duration::from_millis(100);
-                std::thread::sleep(millis);
-                sub.on_next(4);
-                let millis = std::time::Duration::from_millis(100);
-                std::thread::sleep(millis);
-                sub.on_next(5);
-            });
+        let test = TestScheduler::new();
+        let scheduler = test.scheduler();
+        let obs = BaseObservable::<i32, ()>::new(move |sub| {
+            for (millis, items) in [(100, vec![1, 2, 3]), (200, vec![4]), (300, vec![5])] {
+                let sub = sub.clone();
+                scheduler.run_after(Duration::from_millis(millis), move || items.into_iter().for_each(|x| sub.on_next(x)));
+            }
         });
-        let data = Arc::new(Mutex::new(Vec::new()));
-        {
-            let data = data.clone();
-            std::thread::spawn(move || {
-                let data = data.clone();
-                let sub = obs.subscribe(move |x| {
-                    data.lock().unwrap().push(x);
-                });
-
-                let millis = std::time::Duration::from_millis(250);
-                std::thread::sleep(millis);
-                sub.unsubscribe();
-            });
-        }
-
-        assert_eq!(&Vec::<i32>::new(), &*data.lock().unwrap());
-
-        let millis = std::time::Duration::from_millis(150);
-        std::thread::sleep(millis);
-        assert_eq!(&vec![1, 2, 3], &*data.lock().unwrap());
-
-        let millis = std::time::Duration::from_millis(100);
-        std::thread::sleep(millis);
-        assert_eq!(&vec![1, 2, 3, 4], &*data.lock().unwrap());
-
-        let millis = std::time::Duration::from_millis(100);
-        std::thread::sleep(millis);
-        assert_eq!(&vec![1, 2, 3, 4], &*data.lock().unwrap());
+        let observer = TestObserver::new();
+        let sub = obs.subscribe(observer.fork());
+        test.scheduler().run_after(Duration::from_millis(250), move || sub.unsubscribe());
+
+        observer.assert_values(&[]);
+        test.advance_to(Duration::from_millis(150));
+        observer.assert_values(&[1, 2, 3]);
+        test.advance_to(Duration::from_millis(250));
+        observer.assert_values(&[1, 2, 3, 4]);
+        test.advance_to(Duration::from_millis(350));
+        observer.assert_values(&[1, 2, 3, 4]).assert_not_terminated();
     }
 }
 
@@ -1,4 +1,5 @@
 use std::sync::{Mutex, Arc};
+use std::thread::{self, ThreadId};
 use std::time::SystemTime;
 
 pub trait Observer<I, E> {
@@ -8,51 +9,107 @@ pub trait Observer<I, E> {
 }
 
 pub type ObserverId = u32;
-type ObserverBundle<'a, I, E> = Arc<Mutex<Option<Box<dyn BoxedObserver<I, E> + Send + Sync + 'a>>>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification<I, E> {
+    Next(I),
+    Error(E),
+    Completed,
+}
+
+impl<I, E> Notification<I, E> {
+    pub fn is_terminal(&self) -> bool {
+        !matches!(self, Notification::Next(_))
+    }
+}
+
+type Inner<'a, I, E> = Option<Box<dyn BoxedObserver<I, E> + Send + Sync + 'a>>;
+type ObserverBundle<'a, I, E> = Arc<Mutex<Inner<'a, I, E>>>;
+
+#[derive(Default)]
+struct Disposal {
+    disposed: bool,
+    notifying: Option<ThreadId>,
+}
 
 pub struct BaseObserver<'a, I: 'a, E: 'a> {
     id: ObserverId,
-    observer: ObserverBundle<'a, I, E>
+    observer: ObserverBundle<'a, I, E>,
+    disposal: Arc<Mutex<Disposal>>,
 }
 
 impl<'a, I, E> Clone for BaseObserver<'a, I, E> {
     fn clone(&self) -> Self {
-        Self { id: self.id, observer: self.observer.clone() }
+        Self { id: self.id, observer: self.observer.clone(), disposal: self.disposal.clone() }
     }
 }
 
 impl<'a, I, E> BaseObserver<'a, I, E> {
     pub fn new(observer: impl Observer<I, E> + Send + Sync + 'a) -> Self {
         let id = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().subsec_nanos();
-        Self { id, observer: Arc::new(Mutex::new(Some(Box::new(observer)))) }
+        Self { id, observer: Arc::new(Mutex::new(Some(Box::new(observer)))), disposal: Arc::default() }
     }
 
     pub fn id(&self) -> ObserverId {
         self.id
     }
 
+    /// Stops all further notifications and drops the inner observer, waiting for a notification
+    /// in flight on another thread. When called from inside one of this observer's own
+    /// notifications, the inner observer is dropped as soon as that notification returns.
     pub fn dispose(self) {
-        self.observer.lock().unwrap().take();
+        let reentrant = {
+            let mut disposal = self.disposal.lock().unwrap();
+            disposal.disposed = true;
+            disposal.notifying == Some(thread::current().id())
+        };
+        if !reentrant {
+            self.observer.lock().unwrap().take();
+        }
+    }
+
+    fn is_disposed(&self) -> bool {
+        self.disposal.lock().unwrap().disposed
+    }
+
+    /// Runs `notify` on the locked inner observer unless disposed, dropping the inner observer
+    /// afterwards if `dispose` was called meanwhile.
+    fn notify(&self, notify: impl FnOnce(&mut Inner<'a, I, E>)) {
+        let mut observer = self.observer.lock().unwrap();
+        if !self.is_disposed() {
+            self.disposal.lock().unwrap().notifying = Some(thread::current().id());
+            notify(&mut observer);
+            self.disposal.lock().unwrap().notifying = None;
+        }
+        if self.is_disposed() {
+            observer.take();
+        }
     }
 }
 
 impl<'a, I, E> Observer<I, E> for BaseObserver<'a, I, E> {
     fn on_next(&self, item: I) {
-        if let Some(observer) = self.observer.lock().unwrap().as_ref() {
-            observer.on_next(item)
-        }
+        self.notify(|observer| {
+            if let Some(observer) = observer.as_ref() {
+                observer.on_next(item)
+            }
+        })
     }
 
     fn on_error(self, error: E) {
-        if let Some(observer) = self.observer.lock().unwrap().take() {
-            observer.on_error_box(error)
-        }
+        self.notify(|observer| {
+            if let Some(observer) = observer.take() {
+                observer.on_error_box(error)
+            }
+        })
     }
 
     fn on_completed(self) {
-        if let Some(observer) = self.observer.lock().unwrap().take() {
-            observer.on_completed_box()
-        }
+        self.notify(|observer| {
+            if let Some(observer) = observer.take() {
+                observer.on_completed_box()
+            }
+        })
     }
 }
 
@@ -108,3 +165,72 @@ impl<O, I, E> BoxedObserver<I, E> for O where O: Observer<I, E> {
         self.on_error(error);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::BaseObserver;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn dispose_from_on_next() {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let this = Arc::new(Mutex::new(None::<BaseObserver<i32, ()>>));
+        let observer = {
+            let data = data.clone();
+            let this = this.clone();
+            BaseObserver::new(move |x| {
+                data.lock().unwrap().push(x);
+                if let Some(observer) = this.lock().unwrap().take() {
+                    observer.dispose()
+                }
+            })
+        };
+        this.lock().unwrap().replace(observer.clone());
+        observer.on_next(1);
+        assert_eq!(1, Arc::strong_count(&data));
+        observer.on_next(2);
+        assert_eq!(&vec![1], &*data.lock().unwrap());
+    }
+
+    #[test]
+    fn dispose_racing_on_next() {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let finished = Arc::new(AtomicBool::new(false));
+        let (entered, wait_entered) = mpsc::channel();
+        let (release, wait_release) = mpsc::channel::<()>();
+        let observer = {
+            let data = data.clone();
+            let finished = finished.clone();
+            let entered = Mutex::new(entered);
+            let wait_release = Mutex::new(wait_release);
+            BaseObserver::<i32, ()>::new(move |x| {
+                entered.lock().unwrap().send(()).unwrap();
+                wait_release.lock().unwrap().recv().unwrap();
+                data.lock().unwrap().push(x);
+                finished.store(true, Ordering::SeqCst);
+            })
+        };
+        let notifier = {
+            let observer = observer.clone();
+            std::thread::spawn(move || observer.on_next(1))
+        };
+        wait_entered.recv().unwrap();
+        let disposer = {
+            let observer = observer.clone();
+            let finished = finished.clone();
+            std::thread::spawn(move || {
+                observer.dispose();
+                finished.load(Ordering::SeqCst)
+            })
+        };
+        release.send(()).unwrap();
+        notifier.join().unwrap();
+        assert!(disposer.join().unwrap(), "dispose returned before the in-flight on_next finished");
+        assert_eq!(1, Arc::strong_count(&data));
+        observer.on_next(2);
+        assert_eq!(&vec![1], &*data.lock().unwrap());
+    }
+}
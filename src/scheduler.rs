@@ -1,22 +1,333 @@
+use crate::Subscription;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
 type Executor = Box<dyn FnOnce() + Send + Sync + 'static>;
+type Timer = Arc<dyn Fn(Duration, Executor) -> Subscription<'static> + Send + Sync + 'static>;
+type Clock = Arc<dyn Fn() -> Duration + Send + Sync + 'static>;
 
+#[derive(Clone)]
 pub struct Scheduler {
-    executor: Box<dyn FnOnce(Executor) + Send + Sync + 'static>
+    timer: Timer,
+    clock: Clock,
 }
 
 impl Scheduler {
     pub fn new_thread() -> Self {
         Self::new(|f| {
-            std::thread::spawn(move || f());
+            std::thread::spawn(f);
+        })
+    }
+
+    /// Runs work through `executor`. Delayed work waits on a timer thread shared by every
+    /// scheduler and is handed to `executor` once due, on that thread, so an executor that runs
+    /// work inline holds up every other timer until it returns.
+    pub fn new(executor: impl Fn(Executor) + Send + Sync + 'static) -> Self {
+        let executor = Arc::new(executor);
+        let timer = move |delay: Duration, f: Executor| {
+            if delay == Duration::from_secs(0) {
+                executor(f);
+                Subscription::new(|| {})
+            } else {
+                let executor = executor.clone();
+                TimerThread::shared().schedule(Instant::now() + delay, Box::new(move || executor(f)))
+            }
+        };
+        let clock = || SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+        Self::with_timer(timer, clock)
+    }
+
+    pub(crate) fn with_timer<T, C>(timer: T, clock: C) -> Self
+        where T: Fn(Duration, Executor) -> Subscription<'static> + Send + Sync + 'static,
+              C: Fn() -> Duration + Send + Sync + 'static,
+    {
+        Self { timer: Arc::new(timer), clock: Arc::new(clock) }
+    }
+
+    /// Current time of the scheduler's clock, measured from its epoch.
+    pub fn now(&self) -> Duration {
+        (self.clock)()
+    }
+
+    pub fn run(&self, exec: impl FnOnce() + Send + Sync + 'static) {
+        (self.timer)(Duration::from_secs(0), Box::new(exec));
+    }
+
+    /// Runs `exec` once `delay` has passed, unless the returned subscription is unsubscribed first.
+    pub fn run_after(&self, delay: Duration, exec: impl FnOnce() + Send + Sync + 'static) -> Subscription<'static> {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let timer = (self.timer)(delay, Box::new(move || {
+            if !flag.load(Ordering::SeqCst) {
+                exec()
+            }
+        }));
+        Subscription::new(move || {
+            cancelled.store(true, Ordering::SeqCst);
+            timer.unsubscribe()
+        })
+    }
+
+    /// Runs `exec` after `delay` and then every `period` until the returned subscription is unsubscribed.
+    pub fn run_periodic(&self, delay: Duration, period: Duration, exec: impl Fn() + Send + Sync + 'static) -> Subscription<'static> {
+        let periodic = Arc::new(Periodic { cancelled: AtomicBool::new(false), timer: Mutex::new(None) });
+        tick(self.clone(), self.now() + delay, period, Arc::new(exec), periodic.clone());
+        Subscription::new(move || {
+            periodic.cancelled.store(true, Ordering::SeqCst);
+            let timer = periodic.timer.lock().unwrap().take();
+            if let Some(timer) = timer {
+                timer.unsubscribe()
+            }
+        })
+    }
+}
+
+struct Periodic {
+    cancelled: AtomicBool,
+    timer: Mutex<Option<Subscription<'static>>>,
+}
+
+fn tick(scheduler: Scheduler, due: Duration, period: Duration, exec: Arc<dyn Fn() + Send + Sync>, periodic: Arc<Periodic>) {
+    let delay = due.checked_sub(scheduler.now()).unwrap_or_default();
+    let timer = scheduler.timer.clone();
+    let next = periodic.clone();
+    let timer = timer(delay, Box::new(move || {
+        if !next.cancelled.load(Ordering::SeqCst) {
+            exec();
+            tick(scheduler, due + period, period, exec, next);
+        }
+    }));
+    let mut current = periodic.timer.lock().unwrap();
+    if periodic.cancelled.load(Ordering::SeqCst) {
+        drop(current);
+        timer.unsubscribe()
+    } else {
+        current.replace(timer);
+    }
+}
+
+/// Deadline queue served by one background thread, which sleeps on a condvar until the earliest
+/// deadline, or until an earlier one is scheduled.
+struct TimerThread {
+    timers: Mutex<Timers>,
+    wake: Condvar,
+}
+
+#[derive(Default)]
+struct Timers {
+    sequence: u64,
+    queue: BTreeMap<(Instant, u64), Executor>,
+}
+
+impl TimerThread {
+    fn shared() -> &'static Arc<TimerThread> {
+        static SHARED: OnceLock<Arc<TimerThread>> = OnceLock::new();
+        SHARED.get_or_init(|| {
+            let timer = Arc::new(TimerThread { timers: Mutex::default(), wake: Condvar::new() });
+            let worker = timer.clone();
+            std::thread::Builder::new()
+                .name("rx-timer".into())
+                .spawn(move || worker.run())
+                .unwrap();
+            timer
         })
     }
 
-    pub fn new(executor: impl FnOnce(Executor) + Send + Sync + 'static) -> Self {
-        Self { executor: Box::new(executor) }
+    /// Queues `f` for `deadline`. Unsubscribing removes it from the queue.
+    fn schedule(self: &Arc<Self>, deadline: Instant, f: Executor) -> Subscription<'static> {
+        let key = {
+            let mut timers = self.timers.lock().unwrap();
+            let key = (deadline, timers.sequence);
+            timers.sequence += 1;
+            timers.queue.insert(key, f);
+            key
+        };
+        self.wake.notify_one();
+        let timer = self.clone();
+        Subscription::new(move || {
+            timer.timers.lock().unwrap().queue.remove(&key);
+        })
+    }
+
+    fn run(&self) {
+        let mut timers = self.timers.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            match timers.queue.keys().next().copied() {
+                Some(key) if key.0 <= now => {
+                    let task = timers.queue.remove(&key).unwrap();
+                    drop(timers);
+                    // a panicking task must not take down the thread serving every other timer
+                    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(task));
+                    timers = self.timers.lock().unwrap();
+                }
+                Some((deadline, _)) => timers = self.wake.wait_timeout(timers, deadline - now).unwrap().0,
+                None => timers = self.wake.wait(timers).unwrap(),
+            }
+        }
+    }
+}
+
+/// A scheduler driven by a virtual clock, so time based pipelines can be tested
+/// without sleeping. Nothing runs until the clock is moved forward.
+#[derive(Clone, Default)]
+pub struct TestScheduler {
+    state: Arc<Mutex<VirtualTime>>,
+}
+
+#[derive(Default)]
+struct VirtualTime {
+    now: Duration,
+    sequence: u64,
+    queue: BTreeMap<(Duration, u64), Executor>,
+}
+
+impl TestScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A `Scheduler` that queues its work on this virtual clock.
+    pub fn scheduler(&self) -> Scheduler {
+        let timer = {
+            let state = self.state.clone();
+            move |delay, f| {
+                let key = {
+                    let mut state = state.lock().unwrap();
+                    let key = (state.now + delay, state.sequence);
+                    state.sequence += 1;
+                    state.queue.insert(key, f);
+                    key
+                };
+                let state = state.clone();
+                Subscription::new(move || {
+                    state.lock().unwrap().queue.remove(&key);
+                })
+            }
+        };
+        let state = self.state.clone();
+        Scheduler::with_timer(timer, move || state.lock().unwrap().now)
+    }
+
+    pub fn now(&self) -> Duration {
+        self.state.lock().unwrap().now
+    }
+
+    pub fn advance_by(&self, time: Duration) {
+        let target = self.now() + time;
+        self.advance_to(target)
+    }
+
+    /// Runs every task due up to `time`, in order, then moves the clock to `time`.
+    pub fn advance_to(&self, time: Duration) {
+        while let Some(task) = self.next_task(Some(time)) {
+            task()
+        }
+        let mut state = self.state.lock().unwrap();
+        if state.now < time {
+            state.now = time;
+        }
+    }
+
+    /// Runs tasks until the queue is empty. Never returns while a periodic task is active.
+    pub fn run(&self) {
+        while let Some(task) = self.next_task(None) {
+            task()
+        }
+    }
+
+    /// When the earliest queued task is due, if any.
+    pub(crate) fn next_due(&self) -> Option<Duration> {
+        self.state.lock().unwrap().queue.keys().next().map(|(due, _)| *due)
+    }
+
+    fn next_task(&self, until: Option<Duration>) -> Option<Executor> {
+        let mut state = self.state.lock().unwrap();
+        let key = *state.queue.keys().next()?;
+        if until.is_some_and(|until| key.0 > until) {
+            return None;
+        }
+        state.now = key.0;
+        state.queue.remove(&key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::time::Duration;
+    use crate::{Scheduler, TestScheduler};
+
+    #[test]
+    fn virtual_time() {
+        let test = TestScheduler::new();
+        let scheduler = test.scheduler();
+        let data = Arc::new(Mutex::new(Vec::new()));
+        {
+            let later = data.clone();
+            let clock = scheduler.clone();
+            scheduler.run_after(Duration::from_millis(20), move || later.lock().unwrap().push((2, clock.now())));
+            let sooner = data.clone();
+            let clock = scheduler.clone();
+            scheduler.run_after(Duration::from_millis(10), move || sooner.lock().unwrap().push((1, clock.now())));
+            let data = data.clone();
+            scheduler.run_after(Duration::from_millis(15), move || data.lock().unwrap().push((0, Duration::from_millis(0))))
+                .unsubscribe();
+        }
+        assert!(data.lock().unwrap().is_empty());
+
+        test.advance_by(Duration::from_millis(10));
+        assert_eq!(&vec![(1, Duration::from_millis(10))], &*data.lock().unwrap());
+
+        test.run();
+        assert_eq!(&vec![(1, Duration::from_millis(10)), (2, Duration::from_millis(20))], &*data.lock().unwrap());
+        assert_eq!(Duration::from_millis(20), test.now());
+    }
+
+    #[test]
+    fn periodic() {
+        let test = TestScheduler::new();
+        let scheduler = test.scheduler();
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let sub = {
+            let data = data.clone();
+            let clock = scheduler.clone();
+            scheduler.run_periodic(Duration::from_millis(5), Duration::from_millis(10), move || {
+                data.lock().unwrap().push(clock.now().as_millis())
+            })
+        };
+        test.advance_to(Duration::from_millis(30));
+        assert_eq!(&vec![5, 15, 25], &*data.lock().unwrap());
+
+        sub.unsubscribe();
+        test.advance_by(Duration::from_millis(100));
+        assert_eq!(&vec![5, 15, 25], &*data.lock().unwrap());
+    }
+
+    #[test]
+    fn timer_thread() {
+        let scheduler = Scheduler::new_thread();
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let cancelled = {
+            let sender = sender.lock().unwrap().clone();
+            scheduler.run_after(Duration::from_millis(1), move || sender.send("cancelled").unwrap())
+        };
+        cancelled.unsubscribe();
+        scheduler.run_after(Duration::from_millis(20), move || sender.lock().unwrap().send("due").unwrap());
+        assert_eq!(Ok("due"), receiver.recv_timeout(Duration::from_secs(5)));
+        assert!(receiver.try_recv().is_err());
     }
 
-    pub fn run(self, exec: impl FnOnce() + Send + Sync + 'static) {
-        let executor = self.executor;
-        executor(Box::new(exec))
+    #[test]
+    fn timer_thread_survives_panicking_task() {
+        let inline = Scheduler::new(|f| f());
+        inline.run_after(Duration::from_millis(1), || panic!("task failed"));
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        Scheduler::new_thread().run_after(Duration::from_millis(10), move || sender.lock().unwrap().send("due").unwrap());
+        assert_eq!(Ok("due"), receiver.recv_timeout(Duration::from_secs(5)));
     }
 }
@@ -19,6 +19,12 @@ impl<'a, I, E> Subject<'a, I, E> {
     }
 }
 
+impl<'a, I, E> Default for Subject<'a, I, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a, I, E> Observable<'a> for Subject<'a, I, E> {
     type Item = I;
     type Error = E;
@@ -72,10 +78,8 @@ mod tests {
             input.on_next(1);
             input.on_next(2);
             input.on_next(3);
-        });
+        }).join().unwrap();
 
-        let millis = std::time::Duration::from_millis(10);
-        std::thread::sleep(millis);
         assert_eq!(&vec![1, 2, 3], &*data.lock().unwrap());
     }
 }
@@ -0,0 +1,506 @@
+use crate::observable::Observable;
+use crate::observer::Observer;
+use crate::{BaseObserver, Notification, Scheduler, Subscription, TestScheduler};
+use crate::testing::TestObserver;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const FRAME: Duration = Duration::from_millis(1);
+const MAX_FRAMES: u32 = 750;
+
+type Frames<I, E> = Vec<(i64, Notification<I, E>)>;
+type Subscriptions = Vec<(i64, Option<i64>)>;
+
+/// Builds sources from marble diagrams and asserts on what observables emit, on a virtual clock
+/// where every marble character is one frame of `1ms`. Frames count from the moment a source or
+/// expectation is created, so several scenarios can run one after another on the same test.
+///
+/// `-` advances time, `a`-style characters emit the matching value, `|` completes, `#` errors,
+/// `(ab)` groups notifications into a single frame, `^` marks the subscription point and `!` the
+/// unsubscription point. Spaces are ignored.
+#[derive(Default)]
+pub struct MarbleTest {
+    scheduler: TestScheduler,
+}
+
+impl MarbleTest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scheduler to hand to time based operators under test.
+    pub fn scheduler(&self) -> Scheduler {
+        self.scheduler.scheduler()
+    }
+
+    /// Runs the virtual clock until nothing is left scheduled.
+    pub fn flush(&self) {
+        flush(&self.scheduler)
+    }
+
+    pub fn cold<I, E>(&self, marbles: &str, values: &[(char, I)]) -> ColdObservable<I, E> where I: Clone, E: Clone + Default {
+        self.cold_with_error(marbles, values, E::default())
+    }
+
+    pub fn cold_with_error<I, E>(&self, marbles: &str, values: &[(char, I)], error: E) -> ColdObservable<I, E> where I: Clone, E: Clone {
+        let timeline = Timeline::parse(marbles);
+        assert!(timeline.subscribed.is_none(), "cold observable cannot have a subscription point: {:?}", marbles);
+        ColdObservable {
+            events: timeline.notifications(values, &error),
+            scheduler: self.scheduler(),
+            subscriptions: SubscriptionLog::starting_at(self.scheduler.now()),
+        }
+    }
+
+    pub fn hot<I, E>(&self, marbles: &str, values: &[(char, I)]) -> HotObservable<I, E>
+        where I: Clone + Send + Sync + 'static, E: Clone + Default + Send + Sync + 'static
+    {
+        self.hot_with_error(marbles, values, E::default())
+    }
+
+    /// Events before `^` are in the past and never reach subscribers.
+    pub fn hot_with_error<I, E>(&self, marbles: &str, values: &[(char, I)], error: E) -> HotObservable<I, E>
+        where I: Clone + Send + Sync + 'static, E: Clone + Send + Sync + 'static
+    {
+        let timeline = Timeline::parse(marbles);
+        let offset = timeline.subscribed.unwrap_or(0);
+        let scheduler = self.scheduler();
+        let hot = HotObservable {
+            observers: Arc::new(Mutex::new(Vec::new())),
+            scheduler: scheduler.clone(),
+            subscriptions: SubscriptionLog::starting_at(self.scheduler.now()),
+        };
+        for (frame, notification) in timeline.notifications(values, &error) {
+            if frame < offset {
+                continue;
+            }
+            let observers = hot.observers.clone();
+            let subscriptions = hot.subscriptions.clone();
+            let clock = scheduler.clone();
+            scheduler.run_after(frames(frame - offset), move || {
+                let observers = observers.lock().unwrap().clone();
+                if notification.is_terminal() {
+                    subscriptions.close_all(clock.now());
+                }
+                observers.iter().for_each(|observer| deliver(observer, notification.clone()));
+            });
+        }
+        hot
+    }
+
+    /// Subscribes to `observable` at frame zero and records everything it emits.
+    pub fn expect_observable<O>(&self, observable: O) -> ObservableExpectation<O::Item, O::Error>
+        where O: Observable<'static>, O::Item: Send + 'static, O::Error: Send + 'static
+    {
+        let origin = self.scheduler.now();
+        let observer = TestObserver::with_scheduler(&self.scheduler());
+        observable.subscribe(observer.fork());
+        ObservableExpectation { scheduler: self.scheduler.clone(), origin, observer }
+    }
+
+    /// Like `expect_observable`, but subscribes and unsubscribes at the `^` and `!` frames of `subscription`.
+    pub fn expect_observable_with<O>(&self, observable: O, subscription: &str) -> ObservableExpectation<O::Item, O::Error>
+        where O: Observable<'static> + Send + Sync + 'static, O::Item: Send + 'static, O::Error: Send + 'static
+    {
+        let timeline = Timeline::parse(subscription);
+        let subscribed = timeline.subscribed.unwrap_or(0);
+        let origin = self.scheduler.now();
+        let scheduler = self.scheduler();
+        let observer = TestObserver::with_scheduler(&scheduler);
+        let sub = Arc::new(Mutex::new(None));
+        {
+            let observer = observer.fork();
+            let sub = sub.clone();
+            scheduler.run_after(frames(subscribed), move || {
+                sub.lock().unwrap().replace(observable.subscribe(observer));
+            });
+        }
+        if let Some(unsubscribed) = timeline.unsubscribed {
+            scheduler.run_after(frames(unsubscribed), move || {
+                if let Some(sub) = sub.lock().unwrap().take() {
+                    sub.unsubscribe()
+                }
+            });
+        }
+        ObservableExpectation { scheduler: self.scheduler.clone(), origin, observer }
+    }
+
+    pub fn expect_subscriptions(&self, subscriptions: SubscriptionLog) -> SubscriptionExpectation {
+        SubscriptionExpectation { scheduler: self.scheduler.clone(), subscriptions }
+    }
+}
+
+pub struct ColdObservable<I, E> {
+    events: Frames<I, E>,
+    scheduler: Scheduler,
+    subscriptions: SubscriptionLog,
+}
+
+impl<I, E> ColdObservable<I, E> where I: Clone, E: Clone {
+    pub fn fork(&self) -> Self {
+        Self { events: self.events.clone(), scheduler: self.scheduler.clone(), subscriptions: self.subscriptions.clone() }
+    }
+}
+
+impl<I, E> ColdObservable<I, E> {
+    pub fn subscriptions(&self) -> SubscriptionLog {
+        self.subscriptions.clone()
+    }
+}
+
+impl<I, E> Observable<'static> for ColdObservable<I, E> where I: Send + Sync + 'static, E: Send + Sync + 'static {
+    type Item = I;
+    type Error = E;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let scheduler = self.scheduler;
+        let subscriptions = self.subscriptions;
+        let observer = BaseObserver::new(observer);
+        let index = subscriptions.open(scheduler.now());
+        let timers: Vec<_> = self.events.into_iter()
+            .map(|(frame, notification)| {
+                let observer = observer.clone();
+                let subscriptions = subscriptions.clone();
+                let clock = scheduler.clone();
+                scheduler.run_after(frames(frame), move || {
+                    if notification.is_terminal() {
+                        subscriptions.close(index, clock.now());
+                    }
+                    deliver(&observer, notification);
+                })
+            })
+            .collect();
+        Subscription::new(move || {
+            timers.into_iter().for_each(|timer| timer.unsubscribe());
+            subscriptions.close(index, scheduler.now());
+            observer.dispose();
+        })
+    }
+}
+
+pub struct HotObservable<I: 'static, E: 'static> {
+    observers: Arc<Mutex<Vec<BaseObserver<'static, I, E>>>>,
+    scheduler: Scheduler,
+    subscriptions: SubscriptionLog,
+}
+
+impl<I: 'static, E: 'static> HotObservable<I, E> {
+    pub fn fork(&self) -> Self {
+        Self { observers: self.observers.clone(), scheduler: self.scheduler.clone(), subscriptions: self.subscriptions.clone() }
+    }
+
+    pub fn subscriptions(&self) -> SubscriptionLog {
+        self.subscriptions.clone()
+    }
+}
+
+impl<I: 'static, E: 'static> Observable<'static> for HotObservable<I, E> {
+    type Item = I;
+    type Error = E;
+
+    fn subscribe(self, observer: impl Observer<Self::Item, Self::Error> + Send + Sync + 'static) -> Subscription<'static> {
+        let observer = BaseObserver::new(observer);
+        let index = self.subscriptions.open(self.scheduler.now());
+        self.observers.lock().unwrap().push(observer.clone());
+        Subscription::new(move || {
+            self.subscriptions.close(index, self.scheduler.now());
+            observer.dispose();
+        })
+    }
+}
+
+/// Subscription and unsubscription frames of every subscriber of a marble source, counted from
+/// the frame the source was created on.
+#[derive(Clone)]
+pub struct SubscriptionLog {
+    origin: Duration,
+    entries: Arc<Mutex<Subscriptions>>,
+}
+
+impl SubscriptionLog {
+    fn starting_at(origin: Duration) -> Self {
+        Self { origin, entries: Arc::new(Mutex::new(Vec::new())) }
+    }
+
+    fn open(&self, time: Duration) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        entries.push((frame_of(time - self.origin), None));
+        entries.len() - 1
+    }
+
+    fn close(&self, index: usize, time: Duration) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries[index].1.is_none() {
+            entries[index].1 = Some(frame_of(time - self.origin));
+        }
+    }
+
+    fn close_all(&self, time: Duration) {
+        let frame = frame_of(time - self.origin);
+        self.entries.lock().unwrap().iter_mut()
+            .filter(|(_, unsubscribed)| unsubscribed.is_none())
+            .for_each(|(_, unsubscribed)| *unsubscribed = Some(frame));
+    }
+}
+
+pub struct ObservableExpectation<I, E> {
+    scheduler: TestScheduler,
+    origin: Duration,
+    observer: TestObserver<I, E>,
+}
+
+impl<I, E> ObservableExpectation<I, E> where I: Clone + PartialEq + Debug, E: Clone + PartialEq + Debug {
+    pub fn to_be(self, marbles: &str, values: &[(char, I)]) where E: Default {
+        self.to_be_with_error(marbles, values, E::default())
+    }
+
+    /// Flushes the clock and panics with a diff of the notifications if they don't match `marbles`.
+    pub fn to_be_with_error(self, marbles: &str, values: &[(char, I)], error: E) {
+        flush(&self.scheduler);
+        let expected = Timeline::parse(marbles).notifications(values, &error);
+        let actual: Frames<I, E> = self.observer.records().into_iter()
+            .map(|record| (frame_of(record.time - self.origin), record.notification))
+            .collect();
+        if expected != actual {
+            panic!("{}", diff(marbles, values, &expected, &actual));
+        }
+    }
+}
+
+pub struct SubscriptionExpectation {
+    scheduler: TestScheduler,
+    subscriptions: SubscriptionLog,
+}
+
+impl SubscriptionExpectation {
+    /// One `^` (and optional `!`) marble per expected subscription, in subscription order.
+    pub fn to_be(self, marbles: &[&str]) {
+        flush(&self.scheduler);
+        let expected: Vec<_> = marbles.iter()
+            .map(|marbles| {
+                let timeline = Timeline::parse(marbles);
+                let subscribed = timeline.subscribed.unwrap_or_else(|| panic!("subscription marbles need a '^': {:?}", marbles));
+                (subscribed, timeline.unsubscribed)
+            })
+            .collect();
+        let actual = self.subscriptions.entries.lock().unwrap().clone();
+        assert_eq!(expected, actual, "subscriptions do not match {:?}", marbles);
+    }
+}
+
+enum Event {
+    Next(char),
+    Error,
+    Completed,
+}
+
+struct Timeline {
+    events: Vec<(i64, Event)>,
+    subscribed: Option<i64>,
+    unsubscribed: Option<i64>,
+}
+
+impl Timeline {
+    fn parse(marbles: &str) -> Self {
+        let mut timeline = Timeline { events: Vec::new(), subscribed: None, unsubscribed: None };
+        let mut frame = 0;
+        let mut group = None;
+        for c in marbles.chars() {
+            let at = group.unwrap_or(frame);
+            match c {
+                ' ' => continue,
+                '-' => {}
+                '(' => {
+                    assert!(group.is_none(), "nested groups in marbles: {:?}", marbles);
+                    group = Some(frame);
+                }
+                ')' => {
+                    assert!(group.is_some(), "unopened group in marbles: {:?}", marbles);
+                    group = None;
+                }
+                '^' => timeline.subscribed = Some(at),
+                '!' => timeline.unsubscribed = Some(at),
+                '|' => timeline.events.push((at, Event::Completed)),
+                '#' => timeline.events.push((at, Event::Error)),
+                c => timeline.events.push((at, Event::Next(c))),
+            }
+            frame += 1;
+        }
+        assert!(group.is_none(), "unclosed group in marbles: {:?}", marbles);
+        timeline
+    }
+
+    fn notifications<I: Clone, E: Clone>(&self, values: &[(char, I)], error: &E) -> Frames<I, E> {
+        self.events.iter()
+            .map(|(frame, event)| {
+                let notification = match event {
+                    Event::Next(c) => Notification::Next(value_of(*c, values)),
+                    Event::Error => Notification::Error(error.clone()),
+                    Event::Completed => Notification::Completed,
+                };
+                (*frame, notification)
+            })
+            .collect()
+    }
+}
+
+fn value_of<I: Clone>(c: char, values: &[(char, I)]) -> I {
+    values.iter()
+        .find(|(key, _)| *key == c)
+        .map(|(_, value)| value.clone())
+        .unwrap_or_else(|| panic!("no value given for marble {:?}", c))
+}
+
+fn frames(count: i64) -> Duration {
+    FRAME * count as u32
+}
+
+fn frame_of(time: Duration) -> i64 {
+    (time.as_nanos() / FRAME.as_nanos()) as i64
+}
+
+/// Runs every scheduled task, panicking if any is due more than `MAX_FRAMES` frames from now, so
+/// work past the end of the recording, such as a timer that is never cancelled, cannot go unseen.
+fn flush(scheduler: &TestScheduler) {
+    let start = scheduler.now();
+    while let Some(due) = scheduler.next_due() {
+        if due > start + FRAME * MAX_FRAMES {
+            panic!("marble test still has work scheduled at frame {} after running {} frames", frame_of(due - start), MAX_FRAMES);
+        }
+        scheduler.advance_to(due)
+    }
+}
+
+fn deliver<I, E>(observer: &BaseObserver<'static, I, E>, notification: Notification<I, E>) {
+    match notification {
+        Notification::Next(item) => observer.on_next(item),
+        Notification::Error(error) => observer.clone().on_error(error),
+        Notification::Completed => observer.clone().on_completed(),
+    }
+}
+
+fn render<I: PartialEq, E>(events: &[(i64, Notification<I, E>)], values: &[(char, I)]) -> String {
+    let symbol = |notification: &Notification<I, E>| match notification {
+        Notification::Next(item) => values.iter().find(|(_, value)| value == item).map_or('?', |(c, _)| *c),
+        Notification::Error(_) => '#',
+        Notification::Completed => '|',
+    };
+    let mut marbles = String::new();
+    let mut frame = 0;
+    let mut index = 0;
+    while index < events.len() {
+        let at = events[index].0;
+        while frame < at {
+            marbles.push('-');
+            frame += 1;
+        }
+        let group: Vec<char> = events[index..].iter()
+            .take_while(|(frame, _)| *frame == at)
+            .map(|(_, notification)| symbol(notification))
+            .collect();
+        index += group.len();
+        if group.len() > 1 {
+            marbles.push('(');
+            marbles.extend(group.iter());
+            marbles.push(')');
+            frame += group.len() as i64 + 2;
+        } else {
+            marbles.push(group[0]);
+            frame += 1;
+        }
+    }
+    marbles
+}
+
+fn diff<I, E>(marbles: &str, values: &[(char, I)], expected: &[(i64, Notification<I, E>)], actual: &[(i64, Notification<I, E>)]) -> String
+    where I: PartialEq + Debug, E: PartialEq + Debug
+{
+    let mut message = format!(
+        "observable does not match marbles\n  expected: {}\n  actual:   {}\n",
+        marbles.replace(' ', ""),
+        render(actual, values),
+    );
+    // longest common subsequence, so a single missing or extra notification shows up as one line
+    let mut common = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            message += &format!("    frame {}: {:?}\n", expected[i].0, expected[i].1);
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1]) {
+            message += &format!("  - frame {}: {:?}\n", expected[i].0, expected[i].1);
+            i += 1;
+        } else {
+            message += &format!("  + frame {}: {:?}\n", actual[j].0, actual[j].1);
+            j += 1;
+        }
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::MarbleTest;
+    use std::time::Duration;
+
+    #[test]
+    fn cold() {
+        let test = MarbleTest::new();
+        let source = test.cold::<i32, ()>("-a-(bc)-|", &[('a', 1), ('b', 2), ('c', 3)]);
+        let subscriptions = source.subscriptions();
+        test.expect_observable(source.map(|x| x * 10))
+            .to_be("-x-(yz)-|", &[('x', 10), ('y', 20), ('z', 30)]);
+        test.expect_subscriptions(subscriptions).to_be(&["^-------!"]);
+    }
+
+    #[test]
+    fn sequential_scenarios() {
+        let test = MarbleTest::new();
+        let values = [('a', 1), ('b', 2)];
+        test.expect_observable(test.cold::<i32, ()>("-a|", &values)).to_be("-a|", &values);
+        let source = test.cold::<i32, ()>("--b|", &values);
+        let subscriptions = source.subscriptions();
+        test.expect_observable(source).to_be("--b|", &values);
+        test.expect_subscriptions(subscriptions).to_be(&["^--!"]);
+    }
+
+    #[test]
+    fn hot() {
+        let test = MarbleTest::new();
+        let source = test.hot::<i32, &'static str>("-a-^-b-c-#", &[('a', 1), ('b', 2), ('c', 3)]);
+        let subscriptions = source.subscriptions();
+        let expected = test.expect_observable_with(source.fork().filter(|x| *x > 2), "---^-----!");
+        test.expect_observable(source).to_be("--b-c-#", &[('b', 2), ('c', 3)]);
+        expected.to_be("----c-#", &[('c', 3)]);
+        test.expect_subscriptions(subscriptions).to_be(&["^-----!", "---^--!"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "work scheduled at frame 1001")]
+    fn work_past_last_frame() {
+        let test = MarbleTest::new();
+        let source = test.cold::<i32, ()>("-a|", &[('a', 1)]);
+        test.expect_observable(source.delay_on(Duration::from_secs(1), test.scheduler()))
+            .to_be("-|", &[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "actual:   -a-c-|")]
+    fn mismatch() {
+        let test = MarbleTest::new();
+        let source = test.cold::<i32, ()>("-a-c-|", &[('a', 1), ('b', 2), ('c', 3)]);
+        test.expect_observable(source).to_be("-a-b-|", &[('a', 1), ('b', 2), ('c', 3)]);
+    }
+}
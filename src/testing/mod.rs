@@ -0,0 +1,5 @@
+mod marble;
+mod observer;
+
+pub use marble::{MarbleTest, ColdObservable, HotObservable, ObservableExpectation, SubscriptionExpectation, SubscriptionLog};
+pub use observer::{TestObserver, Recorded};
@@ -0,0 +1,169 @@
+use crate::observer::Observer;
+use crate::{Notification, Scheduler};
+use std::fmt::Debug;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::ThreadId;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recorded<I, E> {
+    pub notification: Notification<I, E>,
+    pub time: Duration,
+    pub thread: ThreadId,
+}
+
+type Records<I, E> = Arc<(Mutex<Vec<Recorded<I, E>>>, Condvar)>;
+
+/// Observer that records every notification it receives, for asserting on in tests.
+pub struct TestObserver<I, E> {
+    records: Records<I, E>,
+    clock: Scheduler,
+}
+
+impl<I, E> Default for TestObserver<I, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, E> TestObserver<I, E> {
+    pub fn new() -> Self {
+        Self::with_scheduler(&Scheduler::new_thread())
+    }
+
+    /// Timestamps notifications with the clock of `scheduler`.
+    pub fn with_scheduler(scheduler: &Scheduler) -> Self {
+        Self { records: Arc::new((Mutex::new(Vec::new()), Condvar::new())), clock: scheduler.clone() }
+    }
+
+    pub fn fork(&self) -> Self {
+        Self { records: self.records.clone(), clock: self.clock.clone() }
+    }
+
+    pub fn is_terminated(&self) -> bool {
+        self.records.0.lock().unwrap().iter().any(|record| record.notification.is_terminal())
+    }
+
+    /// Blocks until a terminal notification arrives or `timeout` passes, returning whether it terminated.
+    pub fn await_terminal(&self, timeout: Duration) -> bool {
+        let (records, terminated) = &*self.records;
+        let records = records.lock().unwrap();
+        let (records, _) = terminated
+            .wait_timeout_while(records, timeout, |records| !records.iter().any(|record| record.notification.is_terminal()))
+            .unwrap();
+        records.iter().any(|record| record.notification.is_terminal())
+    }
+
+    fn record(&self, notification: Notification<I, E>) {
+        let (records, terminated) = &*self.records;
+        let terminal = notification.is_terminal();
+        let time = self.clock.now();
+        let thread = std::thread::current().id();
+        records.lock().unwrap().push(Recorded { notification, time, thread });
+        if terminal {
+            terminated.notify_all();
+        }
+    }
+}
+
+impl<I, E> TestObserver<I, E> where I: Clone, E: Clone {
+    pub fn records(&self) -> Vec<Recorded<I, E>> {
+        self.records.0.lock().unwrap().clone()
+    }
+
+    pub fn notifications(&self) -> Vec<Notification<I, E>> {
+        self.records.0.lock().unwrap().iter().map(|record| record.notification.clone()).collect()
+    }
+
+    pub fn values(&self) -> Vec<I> {
+        self.records.0.lock().unwrap().iter()
+            .filter_map(|record| match &record.notification {
+                Notification::Next(item) => Some(item.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl<I, E> TestObserver<I, E> where I: Clone + PartialEq + Debug, E: Clone + PartialEq + Debug {
+    pub fn assert_values(&self, expected: &[I]) -> &Self {
+        assert_eq!(expected, &self.values()[..], "unexpected values");
+        self
+    }
+
+    pub fn assert_completed(&self) -> &Self {
+        let notifications = self.notifications();
+        assert_eq!(Some(&Notification::Completed), notifications.last(), "expected completion, got {:?}", notifications);
+        self
+    }
+
+    pub fn assert_error(&self, error: &E) -> &Self {
+        let notifications = self.notifications();
+        match notifications.last() {
+            Some(Notification::Error(actual)) => assert_eq!(error, actual, "unexpected error"),
+            _ => panic!("expected error {:?}, got {:?}", error, notifications),
+        }
+        self
+    }
+
+    pub fn assert_not_terminated(&self) -> &Self {
+        assert!(!self.is_terminated(), "expected no terminal notification, got {:?}", self.notifications());
+        self
+    }
+}
+
+impl<I, E> Observer<I, E> for TestObserver<I, E> {
+    fn on_next(&self, item: I) {
+        self.record(Notification::Next(item))
+    }
+
+    fn on_error(self, error: E) {
+        self.record(Notification::Error(error))
+    }
+
+    fn on_completed(self) {
+        self.record(Notification::Completed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::testing::TestObserver;
+    use crate::{BaseObservable, Subject, Notification};
+    use std::time::Duration;
+
+    #[test]
+    fn it_works() {
+        let input = Subject::<i32, &'static str>::new();
+        let observer = TestObserver::new();
+        input.fork().map(|x| x * 2).subscribe(observer.fork());
+
+        input.on_next(1);
+        input.on_next(2);
+        observer.assert_values(&[2, 4]).assert_not_terminated();
+
+        input.on_error("failed");
+        observer.assert_values(&[2, 4]).assert_error(&"failed");
+        assert_eq!(vec![Notification::Next(2), Notification::Next(4), Notification::Error("failed")], observer.notifications());
+    }
+
+    #[test]
+    fn await_terminal() {
+        let obs = BaseObservable::<i32, ()>::new(|sub| {
+            std::thread::spawn(move || {
+                sub.on_next(1);
+                std::thread::sleep(Duration::from_millis(10));
+                sub.on_completed();
+            });
+        });
+        let observer = TestObserver::new();
+        obs.subscribe(observer.fork());
+
+        assert!(observer.await_terminal(Duration::from_secs(5)));
+        observer.assert_values(&[1]).assert_completed();
+        let records = observer.records();
+        assert_ne!(std::thread::current().id(), records[0].thread);
+        assert!(records[0].time <= records[1].time);
+    }
+}